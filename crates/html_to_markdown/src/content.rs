@@ -0,0 +1,560 @@
+//! Main content detection in the style of Readability.
+//!
+//! Paragraph-like elements are scored by their text (length and commas) and the scores
+//! are propagated to their ancestors. Ancestors start with a score from their tag,
+//! `<main>`/`role="main"` and class/id hints, and the total is reduced by the link density,
+//! so navigation, menus and lists of related posts lose against the article body.
+
+use crate::dom::{Dom, NodeData, NodeId};
+use std::collections::HashMap;
+
+/// Subtrees that never contain the main content
+const SKIPPED_TAGS: &[&str] = &[
+    "nav", "footer", "aside", "form", "script", "style", "noscript", "template", "svg", "button",
+    "select", "iframe",
+];
+
+/// Elements whose text is scored
+const PARAGRAPH_TAGS: &[&str] = &["p", "pre", "td", "blockquote"];
+
+/// Block elements, a `<div>` or `<section>` without them is scored like a paragraph
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Class/id tokens of content containers
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "blog", "body", "content", "entry", "hentry", "main", "markdown", "page", "post",
+    "prose", "story", "text",
+];
+
+/// Class/id tokens of page chrome
+const NEGATIVE_HINTS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "contact",
+    "cookie",
+    "footer",
+    "header",
+    "hidden",
+    "masthead",
+    "menu",
+    "meta",
+    "modal",
+    "nav",
+    "navbar",
+    "navigation",
+    "newsletter",
+    "pager",
+    "pagination",
+    "popup",
+    "promo",
+    "recommend",
+    "recommended",
+    "related",
+    "share",
+    "sharing",
+    "sidebar",
+    "social",
+    "sponsor",
+    "subscribe",
+    "toolbar",
+    "widget",
+];
+
+/// Weight of a class or id hint and of `<main>`/`role="main"`
+const HINT_WEIGHT: f64 = 25.0;
+/// Paragraphs with less text are not scored
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+/// Number of ancestors a paragraph score is propagated to
+const ANCESTOR_DEPTH: usize = 5;
+/// Candidates within this ratio of the top score are alternatives for the main content
+const ALTERNATIVE_RATIO: f64 = 0.75;
+/// Number of alternatives sharing an ancestor that makes the ancestor the main content
+const MIN_ALTERNATIVES: usize = 3;
+
+/// Candidate root of the main content
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentCandidate {
+    pub node: NodeId,
+    /// Content score after the link density penalty, higher is more likely the main content
+    pub score: f64,
+}
+
+impl ContentCandidate {
+    /// Short selector-like description of the node (`div#content.post-body`) for debugging
+    pub fn describe(&self, dom: &Dom) -> String {
+        let Ok((tag, attrs)) = dom.get_element_data(self.node) else {
+            return self.node.to_string();
+        };
+
+        let mut description = tag.local.to_string();
+        if let Some(id) = attrs.get("id").filter(|id| !id.is_empty()) {
+            description.push('#');
+            description.push_str(id);
+        }
+        if let Some(class) = attrs.get("class") {
+            for token in class.split_whitespace() {
+                description.push('.');
+                description.push_str(token);
+            }
+        }
+        description
+    }
+}
+
+fn tag_name(dom: &Dom, id: NodeId) -> Option<&str> {
+    dom.get_element_data(id)
+        .ok()
+        .map(|(tag, _)| tag.local.as_ref())
+}
+
+fn attr<'a>(dom: &'a Dom, id: NodeId, name: &str) -> Option<&'a str> {
+    dom.get_element_data(id)
+        .ok()
+        .and_then(|(_, attrs)| attrs.get(name))
+        .map(String::as_str)
+}
+
+/// Splits class names and ids into lowercase tokens (`post-body` is `post` and `body`)
+fn hint_tokens(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|token| !token.is_empty())
+        .map(str::to_ascii_lowercase)
+}
+
+fn has_hint(value: &str, hints: &[&str]) -> bool {
+    hint_tokens(value).any(|token| hints.contains(&token.as_str()))
+}
+
+/// Score from the class and id hints, each attribute counts once per direction
+fn hint_weight(dom: &Dom, id: NodeId) -> f64 {
+    ["class", "id"]
+        .iter()
+        .filter_map(|name| attr(dom, id, name))
+        .map(|value| {
+            let mut weight = 0.0;
+            if has_hint(value, NEGATIVE_HINTS) {
+                weight -= HINT_WEIGHT;
+            }
+            if has_hint(value, POSITIVE_HINTS) {
+                weight += HINT_WEIGHT;
+            }
+            weight
+        })
+        .sum()
+}
+
+fn is_main(dom: &Dom, id: NodeId) -> bool {
+    tag_name(dom, id) == Some("main") || attr(dom, id, "role") == Some("main")
+}
+
+/// Elements whose class/id only has chrome hints, like `<div class="sidebar">`
+fn is_unlikely(dom: &Dom, id: NodeId) -> bool {
+    if matches!(tag_name(dom, id), Some("body" | "article" | "main")) || is_main(dom, id) {
+        return false;
+    }
+    let hints: Vec<&str> = ["class", "id"]
+        .iter()
+        .filter_map(|name| attr(dom, id, name))
+        .collect();
+    hints.iter().any(|value| has_hint(value, NEGATIVE_HINTS))
+        && !hints.iter().any(|value| has_hint(value, POSITIVE_HINTS))
+}
+
+fn is_hidden(dom: &Dom, id: NodeId) -> bool {
+    attr(dom, id, "hidden").is_some()
+        || attr(dom, id, "aria-hidden") == Some("true")
+        || attr(dom, id, "style").is_some_and(|style| {
+            let style: String = style.split_whitespace().collect();
+            style.to_ascii_lowercase().contains("display:none")
+        })
+}
+
+fn has_block_children(dom: &Dom, id: NodeId) -> bool {
+    dom.iter_children(id).is_ok_and(|mut children| {
+        children.any(|&child| tag_name(dom, child).is_some_and(|tag| BLOCK_TAGS.contains(&tag)))
+    })
+}
+
+fn is_paragraph(dom: &Dom, id: NodeId) -> bool {
+    match tag_name(dom, id) {
+        Some(tag) if PARAGRAPH_TAGS.contains(&tag) => true,
+        Some("div" | "section") => !has_block_children(dom, id),
+        _ => false,
+    }
+}
+
+/// Initial score of a candidate from its tag and hints
+fn initial_score(dom: &Dom, id: NodeId) -> f64 {
+    let tag_score = match tag_name(dom, id) {
+        Some("article") => 10.0,
+        Some("div") => 5.0,
+        Some("pre" | "td" | "blockquote") => 3.0,
+        Some("address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form") => -3.0,
+        Some("h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th") => -5.0,
+        _ => 0.0,
+    };
+    let main_score = if is_main(dom, id) { HINT_WEIGHT } else { 0.0 };
+    tag_score + main_score + hint_weight(dom, id)
+}
+
+/// Text length in characters and commas, both Latin and CJK
+fn paragraph_score(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let length = text.chars().count();
+    if length < MIN_PARAGRAPH_LENGTH {
+        return None;
+    }
+    let commas = text
+        .chars()
+        .filter(|c| matches!(c, ',' | '、' | '，'))
+        .count();
+    Some(1.0 + commas as f64 + (length as f64 / 100.0).floor().min(3.0))
+}
+
+/// Lengths of the whole text and of the text inside links; in-page links count less
+fn text_lengths(dom: &Dom, id: NodeId, in_link: f64, lengths: &mut (f64, f64)) {
+    let Some(node) = dom.node(id) else {
+        return;
+    };
+    match &node.data {
+        NodeData::Text(text) => {
+            let length = text
+                .split_whitespace()
+                .map(|word| word.chars().count())
+                .sum::<usize>();
+            lengths.0 += length as f64;
+            lengths.1 += length as f64 * in_link;
+        }
+        NodeData::Element { tag, attrs } => {
+            let in_link = match (tag.local.as_ref(), attrs.get("href")) {
+                ("a", Some(href)) if href.starts_with('#') => 0.3,
+                ("a", _) => 1.0,
+                _ => in_link,
+            };
+            for &child in &node.children {
+                text_lengths(dom, child, in_link, lengths);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn link_density(dom: &Dom, id: NodeId) -> f64 {
+    let mut lengths = (0.0, 0.0);
+    text_lengths(dom, id, 0.0, &mut lengths);
+    if lengths.0 == 0.0 {
+        0.0
+    } else {
+        lengths.1 / lengths.0
+    }
+}
+
+/// Scores of the candidates in document order
+struct Scores {
+    order: Vec<NodeId>,
+    scores: HashMap<NodeId, f64>,
+}
+
+impl Scores {
+    fn add(&mut self, dom: &Dom, id: NodeId, score: f64) {
+        let entry = self.scores.entry(id).or_insert_with(|| {
+            self.order.push(id);
+            initial_score(dom, id)
+        });
+        *entry += score;
+    }
+
+    fn score_paragraphs(&mut self, dom: &Dom, id: NodeId) {
+        let Some(node) = dom.node(id) else {
+            return;
+        };
+        let NodeData::Element { tag, .. } = &node.data else {
+            return;
+        };
+        if SKIPPED_TAGS.contains(&tag.local.as_ref()) || is_hidden(dom, id) || is_unlikely(dom, id)
+        {
+            return;
+        }
+
+        if is_paragraph(dom, id) {
+            if let Some(score) = paragraph_score(&dom.collect_text_content(id)) {
+                self.propagate(dom, id, score);
+            }
+            return;
+        }
+
+        for &child in &node.children {
+            self.score_paragraphs(dom, child);
+        }
+    }
+
+    /// Adds a paragraph score to its parent, half to the grandparent and less further up
+    fn propagate(&mut self, dom: &Dom, id: NodeId, score: f64) {
+        let mut current = dom.get_parent(id).ok().flatten();
+        for level in 0..ANCESTOR_DEPTH {
+            let Some(ancestor) = current else {
+                break;
+            };
+            if tag_name(dom, ancestor).is_none() {
+                break;
+            }
+
+            let divider = match level {
+                0 => 1.0,
+                1 => 2.0,
+                _ => level as f64 * 3.0,
+            };
+            self.add(dom, ancestor, score / divider);
+
+            if tag_name(dom, ancestor) == Some("body") {
+                break;
+            }
+            current = dom.get_parent(ancestor).ok().flatten();
+        }
+    }
+
+    /// Candidates with the link density penalty, best first
+    fn ranked(&self, dom: &Dom) -> Vec<ContentCandidate> {
+        let mut candidates: Vec<ContentCandidate> = self
+            .order
+            .iter()
+            .map(|&node| ContentCandidate {
+                node,
+                score: self.scores[&node] * (1.0 - link_density(dom, node)),
+            })
+            .collect();
+        // stable sort keeps document order for equal scores
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates
+    }
+}
+
+/// Moves the top candidate up to the element holding the whole content:
+/// the ancestor shared by several near-equal candidates, wrappers with a single child
+/// and the enclosing `<article>` when the candidate is most of its text
+fn refine(dom: &Dom, candidates: &[ContentCandidate]) -> NodeId {
+    let top = &candidates[0];
+    let mut node = top.node;
+
+    let alternatives: Vec<NodeId> = candidates[1..]
+        .iter()
+        .filter(|candidate| candidate.score >= top.score * ALTERNATIVE_RATIO)
+        .filter(|candidate| !dom.is_ancestor(candidate.node, node))
+        .map(|candidate| candidate.node)
+        .collect();
+    if alternatives.len() >= MIN_ALTERNATIVES {
+        let mut current = dom.get_parent(node).ok().flatten();
+        while let Some(ancestor) = current {
+            if tag_name(dom, ancestor).is_none_or(|tag| tag == "body") {
+                break;
+            }
+            let shared = alternatives
+                .iter()
+                .filter(|&&alternative| dom.is_ancestor(ancestor, alternative))
+                .count();
+            if shared >= MIN_ALTERNATIVES {
+                node = ancestor;
+                break;
+            }
+            current = dom.get_parent(ancestor).ok().flatten();
+        }
+    }
+
+    while let Ok(Some(parent)) = dom.get_parent(node)
+        && tag_name(dom, parent).is_some_and(|tag| tag != "body")
+        && dom.iter_children(parent).is_ok_and(|children| {
+            children
+                .filter(|&&child| tag_name(dom, child).is_some())
+                .count()
+                == 1
+        })
+    {
+        node = parent;
+    }
+
+    // keep the title and header of an <article> around the article body
+    let mut current = Some(node);
+    while let Some(id) = current {
+        if tag_name(dom, id) == Some("article") {
+            let text_length = |id| dom.collect_text_content(id).trim().chars().count();
+            if text_length(node) * 2 >= text_length(id) {
+                node = id;
+            }
+            break;
+        }
+        current = dom.get_parent(id).ok().flatten();
+    }
+    node
+}
+
+/// Finds the root of the main content, none when the page has no scorable text
+pub fn find_main_content(dom: &Dom) -> Option<ContentCandidate> {
+    let body = dom.find_body()?;
+    let mut scores = Scores {
+        order: Vec::new(),
+        scores: HashMap::new(),
+    };
+    scores.score_paragraphs(dom, body);
+
+    let candidates = scores.ranked(dom);
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let node = refine(dom, &candidates);
+    let score = candidates
+        .iter()
+        .find(|candidate| candidate.node == node)
+        .map_or(candidates[0].score, |candidate| candidate.score);
+    Some(ContentCandidate { node, score })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const PARAGRAPH: &str = "Rust is a language empowering everyone to build reliable, efficient software, \
+        with memory safety, thread safety and great tooling.";
+    const JA_PARAGRAPH: &str = "Rustは、信頼性が高く、効率的なソフトウェアを誰もが作れるようにする言語です。\
+        メモリ安全性、スレッド安全性、そして優れたツールを備えています。";
+
+    fn describe(html: &str) -> Option<String> {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        find_main_content(&dom).map(|candidate| candidate.describe(&dom))
+    }
+
+    #[rstest]
+    #[case::no_article(
+        format!(
+            r#"<body><header class="site-header"><a href="/">Home</a></header>
+            <div class="layout"><div class="menu"><ul><li><a href="/a">A</a></li><li><a href="/b">B</a></li></ul></div>
+            <div id="content" class="post-body"><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></div></div>
+            <footer><p>{PARAGRAPH}</p></footer></body>"#
+        ),
+        "div#content.post-body"
+    )]
+    #[case::article_between_sidebars(
+        format!(
+            r#"<body><div class="container"><div class="sidebar"><p>{PARAGRAPH}</p></div>
+            <article class="entry"><h1>Title</h1><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></article>
+            <div class="sidebar related"><p>{PARAGRAPH}</p></div></div></body>"#
+        ),
+        "article.entry"
+    )]
+    #[case::several_articles(
+        format!(
+            r#"<body><main><article id="post"><h1>Title</h1><div class="body"><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></div></article>
+            <section class="recommended"><article><a href="/1">{PARAGRAPH}</a></article><article><a href="/2">{PARAGRAPH}</a></article></section></main></body>"#
+        ),
+        "article#post"
+    )]
+    #[case::role_main(
+        format!(
+            r#"<body><div class="wrapper"><div><p>{PARAGRAPH}</p></div>
+            <div role="main"><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></div></div></body>"#
+        ),
+        "div"
+    )]
+    #[case::link_density(
+        format!(
+            r#"<body><div id="links"><p><a href="/1">{PARAGRAPH}</a></p><p><a href="/2">{PARAGRAPH}</a></p><p><a href="/3">{PARAGRAPH}</a></p></div>
+            <div id="text"><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></div></body>"#
+        ),
+        "div#text"
+    )]
+    #[case::japanese(
+        format!(
+            r#"<body><div class="nav-links"><a href="/">トップ</a></div>
+            <div class="znc"><p>{JA_PARAGRAPH}</p><p>{JA_PARAGRAPH}</p></div></body>"#
+        ),
+        "div.znc"
+    )]
+    #[case::shared_ancestor(
+        format!(
+            r#"<body><div id="chapters"><section class="post"><p>{PARAGRAPH}</p></section><section class="post"><p>{PARAGRAPH}</p></section>
+            <section class="post"><p>{PARAGRAPH}</p></section><section class="post"><p>{PARAGRAPH}</p></section></div><div class="menu"><p>{PARAGRAPH}</p></div></body>"#
+        ),
+        "div#chapters"
+    )]
+    #[case::single_wrapper(
+        format!(r#"<body><div id="outer"><div id="inner"><p>{PARAGRAPH}</p></div></div><p>Short</p></body>"#),
+        "div#outer"
+    )]
+    fn test_find_main_content(#[case] html: String, #[case] expected: &str) {
+        assert_eq!(describe(&html).as_deref(), Some(expected));
+    }
+
+    /// hidden and skipped subtrees are not scored
+    #[rstest]
+    #[case("<body><p>Too short</p></body>")]
+    #[case(format!(r#"<body><nav><p>{PARAGRAPH}</p></nav><aside><p>{PARAGRAPH}</p></aside></body>"#))]
+    #[case(format!(r#"<body><div style="display: none"><p>{PARAGRAPH}</p></div><div hidden><p>{PARAGRAPH}</p></div></body>"#))]
+    fn test_no_main_content(#[case] html: String) {
+        assert_eq!(describe(&html), None);
+    }
+
+    #[test]
+    fn test_score_includes_link_density() {
+        let html = format!(
+            r#"<body><div id="a"><p>{PARAGRAPH}</p></div><div id="b"><p>{PARAGRAPH} <a href="/x">{PARAGRAPH}</a></p></div></body>"#
+        );
+        let dom = parser::parse_html(&html).expect("Failed to parse HTML");
+        let candidate = find_main_content(&dom).expect("No main content");
+
+        assert_eq!(candidate.describe(&dom), "div#a");
+        // p: 1 + 3 commas + 1 for the length, div: 5
+        assert_eq!(candidate.score, 10.0);
+    }
+
+    #[rstest]
+    #[case("https://example.com/", true)]
+    #[case("#section", false)]
+    fn test_in_page_links_count_less(#[case] href: &str, #[case] full_weight: bool) {
+        let html = format!(r#"<body><div><a href="{href}">0123456789</a>0123456789</div></body>"#);
+        let dom = parser::parse_html(&html).expect("Failed to parse HTML");
+        let div = dom
+            .find_element_by_tag(dom.document, "div")
+            .expect("No div");
+        let expected = if full_weight { 0.5 } else { 0.15 };
+        assert!((link_density(&dom, div) - expected).abs() < 1e-9);
+    }
+}
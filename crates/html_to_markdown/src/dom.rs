@@ -1,7 +1,17 @@
+pub mod selector;
+
 use crate::error::ConvertError;
 use html5ever::QualName;
+use selector::Selector;
+use std::cell::OnceCell;
 use std::collections::HashMap;
 
+/// Elements that never have children or a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
 /// Represents a unique identifier for a node in the DOM tree.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct NodeId(usize); // Use usize to represent node IDs
@@ -25,6 +35,8 @@ impl std::fmt::Display for NodeId {
 #[derive(Debug)]
 pub enum NodeData {
     Document,
+    /// Contents of a `<template>`, not attached to the tree
+    DocumentFragment,
     Element {
         tag: QualName,
         attrs: HashMap<String, String>,
@@ -41,12 +53,22 @@ pub struct Node {
     pub children: Vec<NodeId>,
 }
 
+/// Elements by `id` and anchors by `name`, first in document order
+#[derive(Debug, Default)]
+struct IdIndex {
+    ids: HashMap<String, NodeId>,
+    anchor_names: HashMap<String, NodeId>,
+}
+
 /// Custom DOM structure for HTML parsing and manipulation.
 /// It use arena-based architecture to store nodes efficiently.
 #[derive(Debug)]
 pub struct Dom {
     pub arena: Vec<Node>, // all nodes are stored in a single arena
     pub document: NodeId, // root node of the document
+    /// `id` and `<a name>` attributes of the elements in the tree, built on the first lookup
+    /// and reset when the tree is modified through `Dom` methods
+    id_index: OnceCell<IdIndex>,
 }
 
 impl Dom {
@@ -59,6 +81,7 @@ impl Dom {
         Self {
             arena,
             document: NodeId::new(0),
+            id_index: OnceCell::new(),
         }
     }
 
@@ -100,6 +123,7 @@ impl Dom {
 
     /// Creates a node without setting parent-child relationships
     pub fn create_without_parent(&mut self, data: NodeData) -> NodeId {
+        self.id_index.take();
         let id = NodeId::new(self.arena.len());
         self.arena.push(Node {
             data,
@@ -114,6 +138,7 @@ impl Dom {
     }
 
     pub fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.id_index.take();
         self.arena.get_mut(id.as_usize())
     }
 
@@ -138,6 +163,46 @@ impl Dom {
         None
     }
 
+    /// Finds the first element in document order with the given `id` attribute.
+    /// Nodes detached from the tree (like stripped site chrome) are not found.
+    pub fn find_element_by_id(&self, element_id: &str) -> Option<NodeId> {
+        self.index().ids.get(element_id).copied()
+    }
+
+    /// Element targeted by a `#fragment`: the element with the `id`, or else the `<a name>`
+    pub fn find_fragment_target(&self, fragment: &str) -> Option<NodeId> {
+        let index = self.index();
+        index
+            .ids
+            .get(fragment)
+            .or_else(|| index.anchor_names.get(fragment))
+            .copied()
+    }
+
+    fn index(&self) -> &IdIndex {
+        self.id_index.get_or_init(|| {
+            let mut index = IdIndex::default();
+            let mut stack = vec![self.document];
+            while let Some(node_id) = stack.pop() {
+                let Some(node) = self.node(node_id) else {
+                    continue;
+                };
+                if let NodeData::Element { tag, attrs } = &node.data {
+                    if let Some(id) = attrs.get("id") {
+                        index.ids.entry(id.clone()).or_insert(node_id);
+                    }
+                    if tag.local.as_ref() == "a"
+                        && let Some(name) = attrs.get("name")
+                    {
+                        index.anchor_names.entry(name.clone()).or_insert(node_id);
+                    }
+                }
+                stack.extend(node.children.iter().rev());
+            }
+            index
+        })
+    }
+
     pub fn find_all_elements_by_tag(&self, start_id: NodeId, tag_name: &str) -> Vec<NodeId> {
         let mut results = Vec::new();
         self.find_all_elements_by_tag_recursive(start_id, tag_name, &mut results);
@@ -165,6 +230,64 @@ impl Dom {
         }
     }
 
+    /// Checks whether the element has `class_name` among its class tokens
+    pub fn has_class(&self, id: NodeId, class_name: &str) -> bool {
+        self.get_element_data(id).is_ok_and(|(_, attrs)| {
+            attrs
+                .get("class")
+                .is_some_and(|class| class.split_whitespace().any(|token| token == class_name))
+        })
+    }
+
+    /// Checks whether `ancestor` contains `id`
+    pub fn is_ancestor(&self, ancestor: NodeId, id: NodeId) -> bool {
+        let mut current = self.get_parent(id).ok().flatten();
+        while let Some(node_id) = current {
+            if node_id == ancestor {
+                return true;
+            }
+            current = self.get_parent(node_id).ok().flatten();
+        }
+        false
+    }
+
+    /// Elements under `start_id` (included) matching the CSS selector, in document order.
+    /// Like `querySelectorAll`, combinators can match ancestors outside of `start_id`,
+    /// e.g. `article .content > p:not(.ad)`.
+    pub fn select(&self, start_id: NodeId, selector: &str) -> Result<Vec<NodeId>, ConvertError> {
+        Ok(self.select_parsed(start_id, &Selector::parse(selector)?))
+    }
+
+    /// First element under `start_id` (included) matching the CSS selector
+    pub fn select_first(
+        &self,
+        start_id: NodeId,
+        selector: &str,
+    ) -> Result<Option<NodeId>, ConvertError> {
+        let selector = Selector::parse(selector)?;
+        Ok(selector::find_all_elements(self, start_id, true)
+            .into_iter()
+            .find(|&id| selector.matches(self, id)))
+    }
+
+    /// Elements under `start_id` (included) matching a selector parsed once and reused
+    pub fn select_parsed(&self, start_id: NodeId, selector: &Selector) -> Vec<NodeId> {
+        selector::find_all_elements(self, start_id, true)
+            .into_iter()
+            .filter(|&id| selector.matches(self, id))
+            .collect()
+    }
+
+    /// Detaches a node from its parent, so it and its descendants are no longer part of the tree
+    pub fn detach(&mut self, id: NodeId) {
+        let Some(parent_id) = self.node_mut(id).and_then(|node| node.parent.take()) else {
+            return;
+        };
+        if let Some(parent) = self.node_mut(parent_id) {
+            parent.children.retain(|&child_id| child_id != id);
+        }
+    }
+
     pub fn collect_text_content(&self, node_id: NodeId) -> String {
         let Some(node) = self.node(node_id) else {
             return String::new();
@@ -183,6 +306,88 @@ impl Dom {
         }
     }
 
+    /// Serializes a node and its descendants back to HTML.
+    ///
+    /// Used when Markdown cannot represent the content (e.g. block content in table cells).
+    /// Attributes are written in name order so the output is deterministic,
+    /// and `<script>`/`<style>` elements and comments are dropped.
+    pub fn outer_html(&self, node_id: NodeId) -> String {
+        let mut html = String::new();
+        self.write_html(node_id, false, &mut html);
+        html
+    }
+
+    fn write_html(&self, node_id: NodeId, in_pre: bool, html: &mut String) {
+        let Some(node) = self.node(node_id) else {
+            return;
+        };
+
+        match &node.data {
+            NodeData::Text(content) => {
+                if in_pre {
+                    // a blank line would terminate the HTML block, so its line break
+                    // is written as a character reference
+                    let escaped = escape_html(content, false);
+                    let lines: Vec<&str> = escaped.split('\n').collect();
+                    for (i, line) in lines.iter().enumerate() {
+                        if i > 0 {
+                            let is_blank = i + 1 < lines.len() && line.trim().is_empty();
+                            html.push_str(if is_blank { "&#10;" } else { "\n" });
+                        }
+                        html.push_str(line);
+                    }
+                } else {
+                    // blank lines would terminate an HTML block in Markdown
+                    let collapsed = content.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if collapsed.is_empty() {
+                        if !content.is_empty() {
+                            html.push(' ');
+                        }
+                        return;
+                    }
+                    if content.starts_with(char::is_whitespace) {
+                        html.push(' ');
+                    }
+                    html.push_str(&escape_html(&collapsed, false));
+                    if content.ends_with(char::is_whitespace) {
+                        html.push(' ');
+                    }
+                }
+            }
+            NodeData::Element { tag, attrs } => {
+                let name = tag.local.as_ref();
+                if matches!(name, "script" | "style") {
+                    return;
+                }
+
+                html.push('<');
+                html.push_str(name);
+                let mut sorted: Vec<_> = attrs.iter().collect();
+                sorted.sort();
+                for (key, value) in sorted {
+                    html.push_str(&format!(" {key}=\"{}\"", escape_html(value, true)));
+                }
+                html.push('>');
+
+                if VOID_ELEMENTS.contains(&name) {
+                    return;
+                }
+
+                let in_pre = in_pre || name == "pre";
+                for &child_id in &node.children {
+                    self.write_html(child_id, in_pre, html);
+                }
+                html.push_str(&format!("</{name}>"));
+            }
+            NodeData::Document | NodeData::DocumentFragment => {
+                for &child_id in &node.children {
+                    self.write_html(child_id, in_pre, html);
+                }
+            }
+            NodeData::Comment(_) => {}
+        }
+    }
+
     pub fn find_elements_with_attribute(
         &self,
         start_id: NodeId,
@@ -237,11 +442,37 @@ impl Dom {
         self.find_element_by_tag(self.document, "article")
     }
 
+    /// `href` of the `<base>` element, the base URL for relative URLs of the document
+    pub fn find_base_href(&self) -> Option<&str> {
+        let head = self.find_head()?;
+        self.find_all_elements_by_tag(head, "base")
+            .into_iter()
+            .find_map(|id| {
+                let (_, attrs) = self.get_element_data(id).ok()?;
+                attrs.get("href").map(String::as_str)
+            })
+    }
+
     pub fn find_all_meta(&self) -> Vec<NodeId> {
         self.find_all_elements_by_tag(self.document, "meta")
     }
 }
 
+fn escape_html(text: &str, in_attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if in_attribute => escaped.push_str("&quot;"),
+            '\u{00A0}' => escaped.push_str("&nbsp;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 impl Default for Dom {
     fn default() -> Self {
         Self::new()
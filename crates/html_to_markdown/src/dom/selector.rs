@@ -0,0 +1,881 @@
+//! CSS selectors over [`Dom`].
+//!
+//! Supported syntax:
+//! - type (`p`), universal (`*`), class (`.note`), id (`#main`) selectors
+//! - attributes: `[href]`, `[type=a]`, `~=`, `|=`, `^=`, `$=`, `*=`, with the `i` flag
+//! - combinators: descendant (` `), child (`>`), next sibling (`+`), subsequent sibling (`~`)
+//! - selector lists (`h1, h2`)
+//! - pseudo-classes: `:not()`, `:is()`, `:where()`, `:has()`, `:first-child`, `:last-child`,
+//!   `:only-child`, `:nth-child()`, `:nth-last-child()`, `:first-of-type`, `:last-of-type`,
+//!   `:only-of-type`, `:nth-of-type()`, `:nth-last-of-type()`, `:empty`, `:root`
+
+use super::{Dom, NodeData, NodeId};
+use crate::error::ConvertError;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+    NextSibling,
+    SubsequentSibling,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AttrOperator {
+    /// `[attr=value]`
+    Equals,
+    /// `[attr~=value]`, a whitespace-separated token
+    Includes,
+    /// `[attr|=value]`, the value or the value followed by `-`
+    DashMatch,
+    /// `[attr^=value]`
+    Prefix,
+    /// `[attr$=value]`
+    Suffix,
+    /// `[attr*=value]`
+    Substring,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AttrSelector {
+    name: String,
+    /// Operator, value and whether the value is compared case-insensitively
+    condition: Option<(AttrOperator, String, bool)>,
+}
+
+/// `an+b` of the `:nth-*` pseudo-classes
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Nth {
+    a: i64,
+    b: i64,
+}
+
+impl Nth {
+    /// Checks whether the 1-based position is `a*n + b` for some `n >= 0`
+    fn matches(self, position: i64) -> bool {
+        // widened so extreme `a` and `b` from the selector cannot overflow
+        let (a, b) = (i128::from(self.a), i128::from(self.b));
+        let diff = i128::from(position) - b;
+        if a == 0 {
+            diff == 0
+        } else {
+            diff % a == 0 && diff / a >= 0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PseudoClass {
+    Not(SelectorList),
+    Is(SelectorList),
+    Has(Vec<(Combinator, ComplexSelector)>),
+    NthChild { nth: Nth, from_end: bool },
+    NthOfType { nth: Nth, from_end: bool },
+    OnlyChild,
+    OnlyOfType,
+    Empty,
+    Root,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SimpleSelector {
+    Id(String),
+    Class(String),
+    Attr(AttrSelector),
+    Pseudo(PseudoClass),
+}
+
+/// Simple selectors of one element, like `p.note:not(.ad)`
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CompoundSelector {
+    /// Lowercase tag name, none for `*`
+    tag: Option<String>,
+    simple: Vec<SimpleSelector>,
+}
+
+/// Compound selectors joined by combinators, `combinators[i]` is between `compounds[i]` and `compounds[i + 1]`
+#[derive(Debug, Clone, PartialEq)]
+struct ComplexSelector {
+    compounds: Vec<CompoundSelector>,
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SelectorList(Vec<ComplexSelector>);
+
+/// Parsed CSS selector list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector(SelectorList);
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, ConvertError> {
+        let mut parser = Parser {
+            chars: selector.chars().collect(),
+            pos: 0,
+        };
+        let list = parser.parse_list(false)?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(Selector(list))
+    }
+
+    /// Checks whether the element matches the selector
+    pub fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        matches_list(dom, id, &self.0)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> ConvertError {
+        let selector: String = self.chars.iter().collect();
+        ConvertError::InvalidSelector(format!("{message} at {} in \"{selector}\"", self.pos))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn is_ident_char(c: char) -> bool {
+        c.is_alphanumeric() || matches!(c, '-' | '_' | '\\') || !c.is_ascii()
+    }
+
+    /// Identifier with backslash escapes (`md\:flex` is `md:flex`)
+    fn parse_ident(&mut self) -> Result<String, ConvertError> {
+        let mut ident = String::new();
+        while let Some(c) = self.peek().filter(|&c| Self::is_ident_char(c)) {
+            self.pos += 1;
+            if c == '\\' {
+                let escaped = self.peek().ok_or_else(|| self.error("unfinished escape"))?;
+                self.pos += 1;
+                ident.push(escaped);
+            } else {
+                ident.push(c);
+            }
+        }
+        if ident.is_empty() {
+            Err(self.error("expected an identifier"))
+        } else {
+            Ok(ident)
+        }
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String, ConvertError> {
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    let escaped = self.peek().ok_or_else(|| self.error("unfinished escape"))?;
+                    self.pos += 1;
+                    value.push(escaped);
+                }
+                Some(c) => {
+                    self.pos += 1;
+                    value.push(c);
+                }
+            }
+        }
+    }
+
+    /// Comma-separated selectors, up to `)` when nested in a pseudo-class
+    fn parse_list(&mut self, nested: bool) -> Result<SelectorList, ConvertError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.parse_complex(nested)?);
+            self.skip_whitespace();
+            if !self.eat(',') {
+                break;
+            }
+        }
+        Ok(SelectorList(selectors))
+    }
+
+    fn parse_combinator(&mut self) -> Option<Combinator> {
+        let had_whitespace = self.skip_whitespace();
+        let combinator = match self.peek() {
+            Some('>') => Combinator::Child,
+            Some('+') => Combinator::NextSibling,
+            Some('~') => Combinator::SubsequentSibling,
+            Some(',' | ')') | None => return None,
+            _ if had_whitespace => return Some(Combinator::Descendant),
+            _ => return None,
+        };
+        self.pos += 1;
+        self.skip_whitespace();
+        Some(combinator)
+    }
+
+    fn parse_complex(&mut self, nested: bool) -> Result<ComplexSelector, ConvertError> {
+        let mut compounds = vec![self.parse_compound()?];
+        let mut combinators = Vec::new();
+
+        while let Some(combinator) = self.parse_combinator() {
+            combinators.push(combinator);
+            compounds.push(self.parse_compound()?);
+        }
+
+        match self.peek() {
+            Some(')') if nested => {}
+            Some(',') | None => {}
+            _ => return Err(self.error("unexpected character")),
+        }
+        Ok(ComplexSelector {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn parse_compound(&mut self) -> Result<CompoundSelector, ConvertError> {
+        let mut compound = CompoundSelector::default();
+        let start = self.pos;
+
+        if self.eat('*') {
+            // universal selector, no tag constraint
+        } else if self
+            .peek()
+            .is_some_and(|c| Self::is_ident_char(c) && c != '-')
+        {
+            compound.tag = Some(self.parse_ident()?.to_ascii_lowercase());
+        }
+
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    compound
+                        .simple
+                        .push(SimpleSelector::Id(self.parse_ident()?));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    compound
+                        .simple
+                        .push(SimpleSelector::Class(self.parse_ident()?));
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    compound
+                        .simple
+                        .push(SimpleSelector::Attr(self.parse_attribute()?));
+                }
+                Some(':') => {
+                    self.pos += 1;
+                    compound
+                        .simple
+                        .push(SimpleSelector::Pseudo(self.parse_pseudo()?));
+                }
+                _ => break,
+            }
+        }
+
+        if self.pos == start {
+            Err(self.error("expected a selector"))
+        } else {
+            Ok(compound)
+        }
+    }
+
+    fn parse_attribute(&mut self) -> Result<AttrSelector, ConvertError> {
+        self.skip_whitespace();
+        let name = self.parse_ident()?.to_ascii_lowercase();
+        self.skip_whitespace();
+
+        if self.eat(']') {
+            return Ok(AttrSelector {
+                name,
+                condition: None,
+            });
+        }
+
+        let operator = match self.peek() {
+            Some('=') => AttrOperator::Equals,
+            Some('~') => AttrOperator::Includes,
+            Some('|') => AttrOperator::DashMatch,
+            Some('^') => AttrOperator::Prefix,
+            Some('$') => AttrOperator::Suffix,
+            Some('*') => AttrOperator::Substring,
+            _ => return Err(self.error("expected an attribute operator")),
+        };
+        self.pos += 1;
+        if operator != AttrOperator::Equals && !self.eat('=') {
+            return Err(self.error("expected '='"));
+        }
+
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                self.parse_string(quote)?
+            }
+            _ => self.parse_ident()?,
+        };
+        self.skip_whitespace();
+
+        let case_insensitive = if self.peek().is_some_and(|c| c == 'i' || c == 'I') {
+            self.pos += 1;
+            self.skip_whitespace();
+            true
+        } else {
+            if self.peek().is_some_and(|c| c == 's' || c == 'S') {
+                self.pos += 1;
+                self.skip_whitespace();
+            }
+            false
+        };
+
+        if !self.eat(']') {
+            return Err(self.error("expected ']'"));
+        }
+        Ok(AttrSelector {
+            name,
+            condition: Some((operator, value, case_insensitive)),
+        })
+    }
+
+    /// Argument of a functional pseudo-class up to the closing parenthesis
+    fn parse_argument(&mut self) -> Result<String, ConvertError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != ')') {
+            self.pos += 1;
+        }
+        let argument: String = self.chars[start..self.pos].iter().collect();
+        if !self.eat(')') {
+            return Err(self.error("expected ')'"));
+        }
+        Ok(argument)
+    }
+
+    fn parse_nth(&self, argument: &str) -> Result<Nth, ConvertError> {
+        let argument: String = argument
+            .split_whitespace()
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let invalid = || self.error("invalid an+b expression");
+
+        match argument.as_str() {
+            "odd" => return Ok(Nth { a: 2, b: 1 }),
+            "even" => return Ok(Nth { a: 2, b: 0 }),
+            _ => {}
+        }
+
+        match argument.split_once('n') {
+            Some((a, b)) => {
+                let a = match a {
+                    "" | "+" => 1,
+                    "-" => -1,
+                    _ => a.parse().map_err(|_| invalid())?,
+                };
+                // `b` needs its sign after `n` (`2n+1`, not `2n1`)
+                let b = if b.is_empty() {
+                    0
+                } else if b.starts_with(['+', '-']) {
+                    b.parse().map_err(|_| invalid())?
+                } else {
+                    return Err(invalid());
+                };
+                Ok(Nth { a, b })
+            }
+            None => Ok(Nth {
+                a: 0,
+                b: argument.parse().map_err(|_| invalid())?,
+            }),
+        }
+    }
+
+    /// Relative selectors of `:has()`, like `> img` or `.note`
+    fn parse_relative_list(&mut self) -> Result<Vec<(Combinator, ComplexSelector)>, ConvertError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                _ => Combinator::Descendant,
+            };
+            if combinator != Combinator::Descendant {
+                self.pos += 1;
+                self.skip_whitespace();
+            }
+            selectors.push((combinator, self.parse_complex(true)?));
+            self.skip_whitespace();
+            if !self.eat(',') {
+                break;
+            }
+        }
+        Ok(selectors)
+    }
+
+    fn parse_pseudo(&mut self) -> Result<PseudoClass, ConvertError> {
+        let name = self.parse_ident()?.to_ascii_lowercase();
+
+        if !self.eat('(') {
+            return match name.as_str() {
+                "first-child" => Ok(PseudoClass::NthChild {
+                    nth: Nth { a: 0, b: 1 },
+                    from_end: false,
+                }),
+                "last-child" => Ok(PseudoClass::NthChild {
+                    nth: Nth { a: 0, b: 1 },
+                    from_end: true,
+                }),
+                "first-of-type" => Ok(PseudoClass::NthOfType {
+                    nth: Nth { a: 0, b: 1 },
+                    from_end: false,
+                }),
+                "last-of-type" => Ok(PseudoClass::NthOfType {
+                    nth: Nth { a: 0, b: 1 },
+                    from_end: true,
+                }),
+                "only-child" => Ok(PseudoClass::OnlyChild),
+                "only-of-type" => Ok(PseudoClass::OnlyOfType),
+                "empty" => Ok(PseudoClass::Empty),
+                "root" => Ok(PseudoClass::Root),
+                _ => Err(self.error(&format!("unsupported pseudo-class :{name}"))),
+            };
+        }
+
+        let pseudo = match name.as_str() {
+            "not" => PseudoClass::Not(self.parse_list(true)?),
+            "is" | "where" | "matches" => PseudoClass::Is(self.parse_list(true)?),
+            "has" => PseudoClass::Has(self.parse_relative_list()?),
+            "nth-child" | "nth-last-child" | "nth-of-type" | "nth-last-of-type" => {
+                let argument = self.parse_argument()?;
+                let nth = self.parse_nth(&argument)?;
+                let from_end = name.starts_with("nth-last");
+                return Ok(if name.ends_with("of-type") {
+                    PseudoClass::NthOfType { nth, from_end }
+                } else {
+                    PseudoClass::NthChild { nth, from_end }
+                });
+            }
+            _ => return Err(self.error(&format!("unsupported pseudo-class :{name}()"))),
+        };
+
+        self.skip_whitespace();
+        if !self.eat(')') {
+            return Err(self.error("expected ')'"));
+        }
+        Ok(pseudo)
+    }
+}
+
+fn element(dom: &Dom, id: NodeId) -> Option<(&str, &HashMap<String, String>)> {
+    dom.get_element_data(id)
+        .ok()
+        .map(|(tag, attrs)| (tag.local.as_ref(), attrs))
+}
+
+fn parent_element(dom: &Dom, id: NodeId) -> Option<NodeId> {
+    dom.get_parent(id)
+        .ok()
+        .flatten()
+        .filter(|&parent| element(dom, parent).is_some())
+}
+
+/// Element siblings of a node, including the node itself
+fn element_siblings(dom: &Dom, id: NodeId) -> Vec<NodeId> {
+    let Some(parent) = dom.get_parent(id).ok().flatten() else {
+        return vec![id];
+    };
+    dom.iter_children(parent)
+        .map(|children| {
+            children
+                .copied()
+                .filter(|&child| element(dom, child).is_some())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn previous_siblings(dom: &Dom, id: NodeId) -> Vec<NodeId> {
+    let siblings = element_siblings(dom, id);
+    let index = siblings
+        .iter()
+        .position(|&sibling| sibling == id)
+        .unwrap_or(0);
+    siblings[..index].iter().rev().copied().collect()
+}
+
+fn matches_attr(attrs: &HashMap<String, String>, selector: &AttrSelector) -> bool {
+    let Some(actual) = attrs.get(&selector.name) else {
+        return false;
+    };
+    let Some((operator, expected, case_insensitive)) = &selector.condition else {
+        return true;
+    };
+
+    let (actual, expected) = if *case_insensitive {
+        (actual.to_lowercase(), expected.to_lowercase())
+    } else {
+        (actual.clone(), expected.clone())
+    };
+    match operator {
+        AttrOperator::Equals => actual == expected,
+        AttrOperator::Includes => actual.split_whitespace().any(|token| token == expected),
+        AttrOperator::DashMatch => {
+            actual == expected || actual.starts_with(&format!("{expected}-"))
+        }
+        AttrOperator::Prefix => !expected.is_empty() && actual.starts_with(&expected),
+        AttrOperator::Suffix => !expected.is_empty() && actual.ends_with(&expected),
+        AttrOperator::Substring => !expected.is_empty() && actual.contains(&expected),
+    }
+}
+
+fn matches_pseudo(dom: &Dom, id: NodeId, pseudo: &PseudoClass) -> bool {
+    match pseudo {
+        PseudoClass::Not(list) => !matches_list(dom, id, list),
+        PseudoClass::Is(list) => matches_list(dom, id, list),
+        PseudoClass::Has(relative) => relative.iter().any(|(combinator, complex)| {
+            let candidates = match combinator {
+                Combinator::Descendant | Combinator::Child => find_all_elements(dom, id, false),
+                Combinator::NextSibling | Combinator::SubsequentSibling => {
+                    let siblings = element_siblings(dom, id);
+                    let index = siblings.iter().position(|&sibling| sibling == id);
+                    index.map_or_else(Vec::new, |index| siblings[index + 1..].to_vec())
+                }
+            };
+            candidates
+                .into_iter()
+                .any(|candidate| matches_complex(dom, candidate, complex, Some((*combinator, id))))
+        }),
+        PseudoClass::NthChild { nth, from_end } => {
+            position(&element_siblings(dom, id), id, *from_end).is_some_and(|p| nth.matches(p))
+        }
+        PseudoClass::NthOfType { nth, from_end } => {
+            position(&siblings_of_type(dom, id), id, *from_end).is_some_and(|p| nth.matches(p))
+        }
+        PseudoClass::OnlyChild => element_siblings(dom, id).len() == 1,
+        PseudoClass::OnlyOfType => siblings_of_type(dom, id).len() == 1,
+        PseudoClass::Empty => dom.node(id).is_some_and(|node| {
+            node.children.iter().all(|&child| match dom.node(child) {
+                Some(child) => match &child.data {
+                    NodeData::Text(text) => text.is_empty(),
+                    NodeData::Comment(_) => true,
+                    _ => false,
+                },
+                None => true,
+            })
+        }),
+        PseudoClass::Root => parent_element(dom, id).is_none(),
+    }
+}
+
+fn siblings_of_type(dom: &Dom, id: NodeId) -> Vec<NodeId> {
+    let tag = element(dom, id).map(|(tag, _)| tag);
+    element_siblings(dom, id)
+        .into_iter()
+        .filter(|&sibling| element(dom, sibling).map(|(tag, _)| tag) == tag)
+        .collect()
+}
+
+/// 1-based position among the siblings, counted from the end when `from_end`
+fn position(siblings: &[NodeId], id: NodeId, from_end: bool) -> Option<i64> {
+    let index = siblings.iter().position(|&sibling| sibling == id)?;
+    let position = if from_end {
+        siblings.len() - index
+    } else {
+        index + 1
+    };
+    Some(position as i64)
+}
+
+fn matches_compound(dom: &Dom, id: NodeId, compound: &CompoundSelector) -> bool {
+    let Some((tag, attrs)) = element(dom, id) else {
+        return false;
+    };
+    if compound
+        .tag
+        .as_deref()
+        .is_some_and(|expected| expected != tag)
+    {
+        return false;
+    }
+
+    compound.simple.iter().all(|simple| match simple {
+        SimpleSelector::Id(expected) => attrs.get("id").is_some_and(|id| id == expected),
+        SimpleSelector::Class(class_name) => dom.has_class(id, class_name),
+        SimpleSelector::Attr(selector) => matches_attr(attrs, selector),
+        SimpleSelector::Pseudo(pseudo) => matches_pseudo(dom, id, pseudo),
+    })
+}
+
+/// Matches the compound at `index` and the compounds on its left, right to left.
+/// `anchor` is the element a relative selector of `:has()` starts from.
+fn matches_from(
+    dom: &Dom,
+    id: NodeId,
+    complex: &ComplexSelector,
+    index: usize,
+    anchor: Option<(Combinator, NodeId)>,
+) -> bool {
+    if !matches_compound(dom, id, &complex.compounds[index]) {
+        return false;
+    }
+
+    if index == 0 {
+        return match anchor {
+            None => true,
+            Some((Combinator::Descendant, anchor)) => dom.is_ancestor(anchor, id),
+            Some((Combinator::Child, anchor)) => parent_element(dom, id) == Some(anchor),
+            Some((Combinator::NextSibling, anchor)) => {
+                previous_siblings(dom, id).first() == Some(&anchor)
+            }
+            Some((Combinator::SubsequentSibling, anchor)) => {
+                previous_siblings(dom, id).contains(&anchor)
+            }
+        };
+    }
+
+    let next = |candidate| matches_from(dom, candidate, complex, index - 1, anchor);
+    match complex.combinators[index - 1] {
+        Combinator::Child => parent_element(dom, id).is_some_and(next),
+        Combinator::Descendant => {
+            let mut current = parent_element(dom, id);
+            while let Some(ancestor) = current {
+                if next(ancestor) {
+                    return true;
+                }
+                current = parent_element(dom, ancestor);
+            }
+            false
+        }
+        Combinator::NextSibling => previous_siblings(dom, id).first().is_some_and(|&s| next(s)),
+        Combinator::SubsequentSibling => previous_siblings(dom, id).into_iter().any(next),
+    }
+}
+
+fn matches_complex(
+    dom: &Dom,
+    id: NodeId,
+    complex: &ComplexSelector,
+    anchor: Option<(Combinator, NodeId)>,
+) -> bool {
+    matches_from(dom, id, complex, complex.compounds.len() - 1, anchor)
+}
+
+fn matches_list(dom: &Dom, id: NodeId, list: &SelectorList) -> bool {
+    list.0
+        .iter()
+        .any(|complex| matches_complex(dom, id, complex, None))
+}
+
+/// Elements under `start` in document order, `start` itself is included when `include_start`
+pub(super) fn find_all_elements(dom: &Dom, start: NodeId, include_start: bool) -> Vec<NodeId> {
+    let mut results = Vec::new();
+    let mut stack = vec![start];
+    while let Some(id) = stack.pop() {
+        if (id != start || include_start) && element(dom, id).is_some() {
+            results.push(id);
+        }
+        if let Some(node) = dom.node(id) {
+            stack.extend(node.children.iter().rev());
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r#"<html lang="en-US"><head><title>T</title></head><body>
+<nav id="menu"><ul><li><a href="/">Home</a></li><li class="active"><a href="/docs">Docs</a></li></ul></nav>
+<article class="post featured" data-kind="Tutorial">
+<h1 id="title">Title</h1>
+<div class="content"><p id="p1">First</p><p id="p2" class="ad sponsored">Ad</p><span id="s1"></span><p id="p3" class="note">Third</p><div id="inner"><p id="p4">Nested</p></div></div>
+<ul class="tags"><li id="t1"><a href="https://example.com/tags/rust" rel="tag nofollow">rust</a></li><li id="t2"><a href="https://example.com/tags/wasm">wasm</a></li><li id="t3"><a href="/tags/cli.html">cli</a></li></ul>
+<img id="i1" src="a.png" alt=""><img id="i2" src="b.JPG">
+</article>
+<footer><p id="f1">Footer</p></footer>
+</body></html>"#;
+
+    /// description of the matched elements: the id, or the tag name
+    fn select(selector: &str) -> Vec<String> {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        dom.select(dom.document, selector)
+            .expect("Failed to parse selector")
+            .into_iter()
+            .map(|id| {
+                let (tag, attrs) = dom.get_element_data(id).expect("Not an element");
+                attrs
+                    .get("id")
+                    .cloned()
+                    .unwrap_or_else(|| tag.local.to_string())
+            })
+            .collect()
+    }
+
+    /// type, universal, class and id selectors
+    #[rstest]
+    #[case("h1", vec!["title"])]
+    #[case("H1", vec!["title"])]
+    #[case("#p3", vec!["p3"])]
+    #[case(".note", vec!["p3"])]
+    #[case("p.ad.sponsored", vec!["p2"])]
+    #[case(".ad.note", vec![])]
+    #[case("article.post.featured > h1#title", vec!["title"])]
+    #[case("nav *", vec!["ul", "li", "a", "li", "a"])]
+    #[case("h1, footer p", vec!["title", "f1"])]
+    #[case("footer p, h1", vec!["title", "f1"])]
+    fn test_simple_selectors(#[case] selector: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(select(selector), expected);
+    }
+
+    /// attribute selectors
+    #[rstest]
+    #[case("[alt]", vec!["i1"])]
+    #[case("img[alt=\"\"]", vec!["i1"])]
+    #[case("[data-kind=Tutorial]", vec!["article"])]
+    #[case("[data-kind='tutorial']", vec![])]
+    #[case("[data-kind='tutorial' i]", vec!["article"])]
+    #[case("a[rel~=tag]", vec!["a"])]
+    #[case("[lang|=en]", vec!["html"])]
+    #[case("a[href^='https://']", vec!["a", "a"])]
+    #[case("img[src$='.jpg' i]", vec!["i2"])]
+    #[case("a[href*=\"/tags/\"]", vec!["a", "a", "a"])]
+    #[case("a[href^='']", vec![])]
+    #[case("[ class ~= note ]", vec!["p3"])]
+    fn test_attribute_selectors(#[case] selector: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(select(selector), expected);
+    }
+
+    /// combinators
+    #[rstest]
+    #[case("article p", vec!["p1", "p2", "p3", "p4"])]
+    #[case("article .content > p", vec!["p1", "p2", "p3"])]
+    #[case("article .content > p:not(.ad)", vec!["p1", "p3"])]
+    #[case("#p1 + p", vec!["p2"])]
+    #[case("#p2 + p", vec![])]
+    #[case("#p1 ~ p", vec!["p2", "p3"])]
+    #[case("h1 + .content #inner > p", vec!["p4"])]
+    #[case("body > p", vec![])]
+    #[case("nav li > a", vec!["a", "a"])]
+    #[case("ul li ~ li a", vec!["a", "a", "a"])]
+    #[case("div div p", vec!["p4"])]
+    fn test_combinators(#[case] selector: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(select(selector), expected);
+    }
+
+    /// pseudo-classes
+    #[rstest]
+    #[case(".content > :first-child", vec!["p1"])]
+    #[case(".content > :last-child", vec!["inner"])]
+    #[case("#inner > :only-child", vec!["p4"])]
+    #[case(".tags li:nth-child(2)", vec!["t2"])]
+    #[case(".tags li:nth-child(odd)", vec!["t1", "t3"])]
+    #[case(".tags li:nth-child(even)", vec!["t2"])]
+    #[case(".tags li:nth-child(-n + 2)", vec!["t1", "t2"])]
+    #[case(".tags li:nth-child(2n+3)", vec!["t3"])]
+    #[case(".tags li:nth-last-child(1)", vec!["t3"])]
+    #[case(".tags li:nth-child(n-9223372036854775808)", vec!["t1", "t2", "t3"])]
+    #[case(".tags li:nth-child(-9223372036854775808n+9223372036854775807)", vec![])]
+    #[case(".tags li:nth-child(-9223372036854775808)", vec![])]
+    #[case(".content p:first-of-type", vec!["p1", "p4"])]
+    #[case(".content > p:last-of-type", vec!["p3"])]
+    #[case(".content > p:nth-of-type(2)", vec!["p2"])]
+    #[case(".content > p:nth-last-of-type(2)", vec!["p2"])]
+    #[case("article > img:only-of-type", vec![])]
+    #[case("h1:only-of-type", vec!["title"])]
+    #[case("span:empty", vec!["s1"])]
+    #[case(":root", vec!["html"])]
+    #[case("p:is(.ad, .note)", vec!["p2", "p3"])]
+    #[case("p:where(#p1)", vec!["p1"])]
+    #[case("p:not(.ad, .note)", vec!["p1", "p4", "f1"])]
+    #[case("li:not(:first-child) > a", vec!["a", "a", "a"])]
+    #[case("div:has(> #p4)", vec!["inner"])]
+    #[case("div:has(#p4)", vec!["div", "inner"])]
+    #[case("li:has(a[href^='/'])", vec!["li", "li", "t3"])]
+    #[case("#p1:has(+ p.ad)", vec!["p1"])]
+    #[case("#p1:has(~ .note)", vec!["p1"])]
+    #[case("h1:has(+ .content)", vec!["title"])]
+    fn test_pseudo_classes(#[case] selector: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(select(selector), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("p,")]
+    #[case("> p")]
+    #[case("p >")]
+    #[case("[href")]
+    #[case("[href=]")]
+    #[case("[href^'x']")]
+    #[case("a[href='x]")]
+    #[case("p:hover")]
+    #[case("p:nth-child(x)")]
+    #[case("p:nth-child(2n1)")]
+    #[case("p:nth-child(n2)")]
+    #[case("p:nth-child(2n+-1)")]
+    #[case("p:nth-child(n-9223372036854775809)")]
+    #[case("p:not(.a")]
+    #[case("p..a")]
+    #[case("#")]
+    fn test_invalid_selectors(#[case] selector: &str) {
+        assert!(matches!(
+            Selector::parse(selector),
+            Err(ConvertError::InvalidSelector(_))
+        ));
+    }
+
+    #[test]
+    fn test_select_first_and_start() {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        let article = dom
+            .select_first(dom.document, "article")
+            .expect("Failed to parse selector")
+            .expect("No article");
+
+        // the start element itself can match
+        assert_eq!(dom.select(article, "article").unwrap(), vec![article]);
+        // elements outside of the start are not returned
+        assert!(dom.select(article, "nav, footer p").unwrap().is_empty());
+        // but ancestors outside of the start can match combinators
+        assert_eq!(dom.select(article, "body h1").unwrap().len(), 1);
+        assert_eq!(dom.select_first(article, "footer").unwrap(), None);
+    }
+
+    #[test]
+    fn test_escaped_identifiers() {
+        let dom = parser::parse_html(r#"<div class="md:flex w-1/2">x</div>"#)
+            .expect("Failed to parse HTML");
+        assert_eq!(
+            dom.select(dom.document, r".md\:flex.w-1\/2").unwrap().len(),
+            1
+        );
+    }
+}
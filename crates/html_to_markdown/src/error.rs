@@ -15,6 +15,9 @@ pub enum ConvertError {
     #[error("Invalid List: {0}")]
     InvalidList(String),
 
+    #[error("Invalid selector: {0}")]
+    InvalidSelector(String),
+
     #[error("Unsupported tag: <{0}>")]
     Unsupported(String),
 
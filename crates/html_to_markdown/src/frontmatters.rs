@@ -1,4 +1,4 @@
-use crate::dom::Dom;
+use crate::{dom::Dom, sites::SiteExtractor};
 use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
@@ -14,23 +14,38 @@ pub trait FrontMatter: Sync + Send {
 
 static FRONTMATTER_MAP: LazyLock<HashMap<&'static str, &'static dyn FrontMatter>> =
     LazyLock::new(|| {
-        let mut map = HashMap::new();
-        map.insert("title", &title::EXTRACTOR as &'static dyn FrontMatter);
-        map.insert("source", &source::EXTRACTOR as &'static dyn FrontMatter);
-        // map.insert("tags", &tags::EXTRACTOR as &'static dyn FrontMatter);
-        // map.insert("date", &date::EXTRACTOR as &'static dyn FrontMatter);
-        map
+        let extractors = [
+            &title::EXTRACTOR as &'static dyn FrontMatter,
+            &source::EXTRACTOR as &'static dyn FrontMatter,
+            // &tags::EXTRACTOR as &'static dyn FrontMatter,
+            // &date::EXTRACTOR as &'static dyn FrontMatter,
+        ];
+        extractors
+            .into_iter()
+            .map(|extractor| (extractor.key(), extractor))
+            .collect()
     });
 
-pub fn get_frontmatter_extractors(keys: &[&str]) -> Vec<(&'static str, &'static dyn FrontMatter)> {
+/// Front-matter `key: value` entries in the order of `keys`,
+/// values of the site extractor take priority over the generic extractors
+pub fn extract_frontmatter(
+    url: &str,
+    dom: &Dom,
+    keys: &[&str],
+    site: Option<&dyn SiteExtractor>,
+) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut result = Vec::new();
 
     for &key in keys {
-        if seen.insert(key)
-            && let Some(&extractor) = FRONTMATTER_MAP.get(key)
-        {
-            result.push((extractor.key(), extractor));
+        if !seen.insert(key) {
+            continue;
+        }
+        let value = site
+            .and_then(|site| site.frontmatter(key, url, dom))
+            .or_else(|| FRONTMATTER_MAP.get(key)?.extract(url, dom));
+        if let Some(value) = value {
+            result.push(format!("{key}: {value}"));
         }
     }
     result
@@ -38,7 +38,7 @@ fn extract_head_title(dom: &Dom) -> Option<String> {
     normalize_html_text(&text, false).map(cow_to_string)
 }
 
-fn extract_meta_content(dom: &Dom, attr_name: &str, attr_value: &str) -> Option<String> {
+pub(crate) fn extract_meta_content(dom: &Dom, attr_name: &str, attr_value: &str) -> Option<String> {
     for meta_id in dom.find_all_meta() {
         let Some(node) = dom.node(meta_id) else {
             continue;
@@ -1,12 +1,26 @@
+pub mod content;
 pub mod dom;
 pub mod error;
 mod frontmatters;
+mod options;
 mod parser;
 mod renderers;
+pub mod sites;
 mod utils;
 
 use error::ConvertError;
-use frontmatters::get_frontmatter_extractors;
+use frontmatters::extract_frontmatter;
+
+pub use options::ConvertOptions;
+pub use renderers::{
+    callout::{CalloutTypes, DetailsMode},
+    code_block::FenceStyle,
+    inline::{EmphasisMarker, InlineHtml},
+    list::{BulletMarker, ListNumbering},
+    media::{ImageStyle, LinkStyle},
+    ruby::RubyMode,
+};
+pub use utils::filtering::RemovalRules;
 
 /// Convert HTML to Markdown with front-matter extraction
 ///
@@ -43,6 +57,31 @@ use frontmatters::get_frontmatter_extractors;
 /// ```
 ///
 pub fn convert(url: &str, html: &str, keys: &[&str]) -> Result<String, ConvertError> {
+    convert_with_options(url, html, keys, &ConvertOptions::default())
+}
+
+/// Convert HTML to Markdown with front-matter extraction and output options
+///
+/// [`convert`] is this function with [`ConvertOptions::default`].
+///
+/// # Example
+///
+/// ```rust
+/// use html_to_markdown::{BulletMarker, ConvertOptions};
+///
+/// let options = ConvertOptions::default()
+///     .bullet_marker(BulletMarker::Asterisk)
+///     .heading_top_level(2);
+/// let html = "<h1>Title</h1><ul><li>Item</li></ul>";
+/// let markdown = html_to_markdown::convert_with_options("https://example.com", html, &[], &options);
+/// assert_eq!(markdown.unwrap(), "## Title\n\n* Item\n\n");
+/// ```
+pub fn convert_with_options(
+    url: &str,
+    html: &str,
+    keys: &[&str],
+    options: &ConvertOptions,
+) -> Result<String, ConvertError> {
     // If you want to fetch HTML content from a URL,
     // you can use an HTTP client library like `reqwest` here.
     // (Obsidian need this API, `requestUrl`, so html content is passed directly)
@@ -54,18 +93,14 @@ pub fn convert(url: &str, html: &str, keys: &[&str]) -> Result<String, ConvertEr
     //                  .error_for_status()?;
 
     // parse HTML
-    let dom = parser::parse_html(html)?;
+    let mut dom = parser::parse_html(html)?;
+    let site = sites::find_site_extractor(url, &options.site_extractors);
 
-    // front-matter
-    let extractors = get_frontmatter_extractors(keys);
-    let frontmatter_entries: Vec<String> = extractors
-        .into_iter()
-        .filter_map(|(key, extractor)| {
-            extractor
-                .extract(url, &dom)
-                .map(|val| format!("{key}: {val}"))
-        })
-        .collect();
+    // front-matter, extracted before the site chrome (like tag bars) is stripped
+    let frontmatter_entries = extract_frontmatter(url, &dom, keys, site);
+    if let Some(site) = site {
+        sites::strip_chrome(&mut dom, site);
+    }
 
     let mut markdown = String::new();
     if !frontmatter_entries.is_empty() {
@@ -78,12 +113,71 @@ pub fn convert(url: &str, html: &str, keys: &[&str]) -> Result<String, ConvertEr
     }
 
     // render body
-    let mut ctx = renderers::Context::default();
-    let start_id = dom
-        .find_article()
+    let mut ctx = renderers::Context {
+        options: options.clone(),
+        ..Default::default()
+    };
+    ctx.options.removal_rules = options.removal_rules.for_url(url).clone();
+    let start_id = site
+        .and_then(|site| site.content_root(url, &dom))
+        .or_else(|| content::find_main_content(&dom).map(|candidate| candidate.node))
+        .or_else(|| dom.find_article())
         .or_else(|| dom.find_body())
         .unwrap_or(dom.document);
-    let body = renderers::render_node(url, &dom, start_id, &mut ctx)?;
+    ctx.render_root = Some(start_id);
+    if let Some(top_level) = options.heading_top_level {
+        ctx.heading_offset = renderers::heading::Heading::level_offset(&dom, start_id, top_level);
+    }
+    // relative URLs are resolved against <base href> when the document has one
+    let base_url = dom
+        .find_base_href()
+        .map(|href| utils::url::resolve_url(url, href))
+        .unwrap_or_else(|| url.to_string());
+    // a table cell root (GitHub comment bodies) is rendered as a block, not as a cell
+    let is_table_cell = dom
+        .get_element_data(start_id)
+        .is_ok_and(|(tag, _)| matches!(tag.local.as_ref(), "td" | "th"));
+    let body = if is_table_cell {
+        renderers::render_children(&base_url, &dom, start_id, &mut ctx)?
+    } else {
+        renderers::render_node(&base_url, &dom, start_id, &mut ctx)?
+    };
     markdown.push_str(&body);
+
+    // reference-style link definitions
+    let references = renderers::media::Media::reference_definitions(&ctx);
+    if !references.is_empty() {
+        if !markdown.is_empty() && !markdown.ends_with("\n\n") {
+            markdown.push_str(if markdown.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        markdown.push_str(&references);
+    }
     Ok(markdown)
 }
+
+/// Main content chosen as the render root, for debugging the content detection
+///
+/// # Returns
+///
+/// * The description of the chosen element (`div#content.post-body`) and its content score,
+///   or `None` when the page has no scorable text and the `<article>` or `<body>` is used.
+///
+/// # Example
+///
+/// ```rust
+/// let html = r#"<body><nav><a href="/">Home</a></nav><div id="post">
+/// <p>Rust is a language empowering everyone to build reliable, efficient software.</p>
+/// </div></body>"#;
+/// let (element, score) = html_to_markdown::detect_main_content(html).unwrap().unwrap();
+/// assert_eq!(element, "div#post");
+/// assert!(score > 0.0);
+/// ```
+pub fn detect_main_content(html: &str) -> Result<Option<(String, f64)>, ConvertError> {
+    let dom = parser::parse_html(html)?;
+    Ok(content::find_main_content(&dom)
+        .map(|candidate| (candidate.describe(&dom), candidate.score)))
+}
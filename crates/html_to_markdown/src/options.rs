@@ -0,0 +1,266 @@
+use crate::renderers::{
+    callout::{CalloutTypes, DetailsMode},
+    code_block::FenceStyle,
+    inline::{EmphasisMarker, InlineHtml},
+    list::{BulletMarker, ListNumbering},
+    media::{ImageStyle, LinkStyle},
+    ruby::RubyMode,
+};
+use crate::sites::SiteExtractor;
+use crate::utils::filtering::RemovalRules;
+
+/// Options controlling the Markdown output of [`crate::convert_with_options`].
+///
+/// Options are set with builder methods on top of the defaults used by [`crate::convert`].
+///
+/// # Example
+///
+/// ```rust
+/// use html_to_markdown::{BulletMarker, ConvertOptions, FenceStyle};
+///
+/// let options = ConvertOptions::default()
+///     .bullet_marker(BulletMarker::Asterisk)
+///     .code_fence(FenceStyle::Tilde)
+///     .callout_type("custom-box", "quote");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConvertOptions {
+    pub(crate) bullet_marker: BulletMarker,
+    pub(crate) emphasis_marker: EmphasisMarker,
+    pub(crate) list_numbering: ListNumbering,
+    pub(crate) code_fence: FenceStyle,
+    pub(crate) callout_types: CalloutTypes,
+    pub(crate) details: DetailsMode,
+    pub(crate) inline_html: InlineHtml,
+    pub(crate) ruby: RubyMode,
+    pub(crate) images: ImageStyle,
+    pub(crate) link_style: LinkStyle,
+    pub(crate) skip_nav_footer: bool,
+    pub(crate) removal_rules: RemovalRules,
+    pub(crate) heading_top_level: Option<usize>,
+    pub(crate) heading_block_ids: bool,
+    pub(crate) site_extractors: Vec<&'static dyn SiteExtractor>,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            bullet_marker: BulletMarker::default(),
+            emphasis_marker: EmphasisMarker::default(),
+            list_numbering: ListNumbering::default(),
+            code_fence: FenceStyle::default(),
+            callout_types: CalloutTypes::default(),
+            details: DetailsMode::default(),
+            inline_html: InlineHtml::default(),
+            ruby: RubyMode::default(),
+            images: ImageStyle::default(),
+            link_style: LinkStyle::default(),
+            skip_nav_footer: true,
+            removal_rules: RemovalRules::default(),
+            heading_top_level: None,
+            heading_block_ids: false,
+            site_extractors: Vec::new(),
+        }
+    }
+}
+
+impl ConvertOptions {
+    /// Marker of unordered list items (`-` by default)
+    pub fn bullet_marker(mut self, marker: BulletMarker) -> Self {
+        self.bullet_marker = marker;
+        self
+    }
+
+    /// Delimiter of emphasis and strong emphasis (`*` by default)
+    pub fn emphasis_marker(mut self, marker: EmphasisMarker) -> Self {
+        self.emphasis_marker = marker;
+        self
+    }
+
+    /// Numbering of ordered list items
+    pub fn list_numbering(mut self, numbering: ListNumbering) -> Self {
+        self.list_numbering = numbering;
+        self
+    }
+
+    /// Fence character of code blocks
+    pub fn code_fence(mut self, style: FenceStyle) -> Self {
+        self.code_fence = style;
+        self
+    }
+
+    /// Maps an admonition class name to an Obsidian callout type
+    pub fn callout_type(mut self, class_name: &str, callout_type: &str) -> Self {
+        self.callout_types.insert(class_name, callout_type);
+        self
+    }
+
+    /// Output mode for `<details>`
+    pub fn details(mut self, mode: DetailsMode) -> Self {
+        self.details = mode;
+        self
+    }
+
+    /// Output mode for `<sub>`, `<sup>`, `<kbd>` and `<abbr>`
+    pub fn inline_html(mut self, mode: InlineHtml) -> Self {
+        self.inline_html = mode;
+        self
+    }
+
+    /// Output mode for `<ruby>` annotations
+    pub fn ruby(mut self, mode: RubyMode) -> Self {
+        self.ruby = mode;
+        self
+    }
+
+    /// Output mode for images
+    pub fn images(mut self, style: ImageStyle) -> Self {
+        self.images = style;
+        self
+    }
+
+    /// Inline links or reference-style links with a reference list at the end
+    pub fn link_style(mut self, style: LinkStyle) -> Self {
+        self.link_style = style;
+        self
+    }
+
+    /// Whether `<nav>` and `<footer>` are left out (true by default)
+    pub fn skip_nav_footer(mut self, skip: bool) -> Self {
+        self.skip_nav_footer = skip;
+        self
+    }
+
+    /// Rules for elements left out of the output, like sidebars and share buttons
+    pub fn removal_rules(mut self, rules: RemovalRules) -> Self {
+        self.removal_rules = rules;
+        self
+    }
+
+    /// Shifts heading levels so the highest heading of the content gets `level` (1-6),
+    /// e.g. 2 when the title is kept in front matter
+    pub fn heading_top_level(mut self, level: usize) -> Self {
+        self.heading_top_level = Some(level.clamp(1, 6));
+        self
+    }
+
+    /// Appends `^block-id` from the heading `id` attribute
+    pub fn heading_block_ids(mut self, enabled: bool) -> Self {
+        self.heading_block_ids = enabled;
+        self
+    }
+
+    /// Adds a site extractor, checked before the built-in ones for Qiita, Zenn, note.com,
+    /// Hatena Blog and GitHub
+    pub fn site_extractor(mut self, extractor: &'static dyn SiteExtractor) -> Self {
+        self.site_extractors.push(extractor);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_with_options;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r#"<html><body>
+<nav><a href="/home">Home</a></nav>
+<h2 id="intro">Intro</h2>
+<p><em>Some</em> text with <strong>bold</strong> and <img src="/a.png" alt="Chart"></p>
+<ul><li>One</li><li>Two</li></ul>
+<pre><code class="language-rust">fn main() {}</code></pre>
+<footer>Footer</footer>
+</body></html>"#;
+
+    #[rstest]
+    #[case(
+        ConvertOptions::default(),
+        indoc! {r#"
+            ## Intro
+
+            *Some* text with **bold** and ![Chart](https://example.com/a.png)
+
+            - One
+            - Two
+
+            ```rust
+            fn main() {}
+            ```
+
+            "#}
+    )]
+    #[case(
+        ConvertOptions::default()
+            .bullet_marker(BulletMarker::Asterisk)
+            .emphasis_marker(EmphasisMarker::Underscore)
+            .code_fence(FenceStyle::Tilde)
+            .images(ImageStyle::AltText),
+        indoc! {r#"
+            ## Intro
+
+            _Some_ text with __bold__ and Chart
+
+            * One
+            * Two
+
+            ~~~rust
+            fn main() {}
+            ~~~
+
+            "#}
+    )]
+    #[case(
+        ConvertOptions::default()
+            .bullet_marker(BulletMarker::Plus)
+            .heading_top_level(1)
+            .heading_block_ids(true),
+        indoc! {r#"
+            # Intro ^intro
+
+            *Some* text with **bold** and ![Chart](https://example.com/a.png)
+
+            + One
+            + Two
+
+            ```rust
+            fn main() {}
+            ```
+
+            "#}
+    )]
+    fn test_convert_with_options(#[case] options: ConvertOptions, #[case] expected: &str) {
+        let markdown = convert_with_options("https://example.com", HTML, &[], &options)
+            .expect("Failed to convert HTML");
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn test_reference_links() {
+        let html =
+            r#"<p>See <a href="/a">A</a>, <a href="/b">B</a> and <a href="/a">A again</a></p>"#;
+        let options = ConvertOptions::default().link_style(LinkStyle::Referenced);
+        let markdown = convert_with_options("https://example.com", html, &[], &options)
+            .expect("Failed to convert HTML");
+        assert_eq!(
+            markdown,
+            indoc! {r#"
+                See [A][1], [B][2] and [A again][1]
+
+                [1]: https://example.com/a
+                [2]: https://example.com/b
+                "#}
+        );
+    }
+
+    #[test]
+    fn test_keep_nav_footer() {
+        let options = ConvertOptions::default().skip_nav_footer(false);
+        let markdown = convert_with_options("https://example.com", HTML, &[], &options)
+            .expect("Failed to convert HTML");
+        assert!(markdown.starts_with("[Home](https://example.com/home)"));
+        assert!(markdown.trim_end().ends_with("Footer"));
+    }
+}
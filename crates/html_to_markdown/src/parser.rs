@@ -12,16 +12,21 @@ use std::collections::HashMap;
 use std::default::Default;
 
 pub fn parse_html(html: &str) -> Result<Dom, ConvertError> {
+    let sink = parse_into_sink(html)?;
+    Ok(RefCell::into_inner(sink.dom)) // RefCell<Dom> -> Dom
+}
+
+fn parse_into_sink(html: &str) -> Result<VecSink, ConvertError> {
     let sink = VecSink {
         dom: RefCell::new(Dom::new()),
         // Store leaked QualName references for efficient elem_name lookups
         element_names: RefCell::new(HashMap::new()),
+        template_contents: RefCell::new(HashMap::new()),
     };
-    let sink = html5ever::parse_document(sink, Default::default())
+    html5ever::parse_document(sink, Default::default())
         .from_utf8()
         .read_from(&mut html.as_bytes())
-        .map_err(|e| ConvertError::Parse(e.to_string()))?;
-    Ok(RefCell::into_inner(sink.dom)) // RefCell<Dom> -> Dom
+        .map_err(|e| ConvertError::Parse(e.to_string()))
 }
 
 struct VecSink {
@@ -30,6 +35,9 @@ struct VecSink {
     /// Cache of leaked QualName references for efficient elem_name implementation
     /// Following the approach demonstrated in html5ever's official examples
     element_names: RefCell<HashMap<NodeId, &'static QualName>>,
+    /// Document fragment holding the contents of each `<template>` element.
+    /// Like in browsers, the contents are not children of the template and are not rendered.
+    template_contents: RefCell<HashMap<NodeId, NodeId>>,
 }
 
 impl VecSink {
@@ -37,6 +45,33 @@ impl VecSink {
         let mut dom = self.dom.borrow_mut();
         f(&mut dom)
     }
+
+    /// Appends text to `text_id` when it is a text node, to merge the chunks of a run of text
+    fn merge_text(dom: &mut Dom, text_id: Option<NodeId>, text: &str) -> bool {
+        if let Some(id) = text_id
+            && let Some(NodeData::Text(existing)) = dom.node_mut(id).map(|n| &mut n.data)
+        {
+            existing.push_str(text);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves `child` into the children of `parent`, before `sibling` or at the end.
+    /// The position is found after detaching, as `child` may already be a child of `parent`.
+    fn insert_child(dom: &mut Dom, parent: NodeId, sibling: Option<NodeId>, child: NodeId) {
+        dom.detach(child);
+        if let Some(node) = dom.node_mut(child) {
+            node.parent = Some(parent);
+        }
+        if let Some(parent_node) = dom.node_mut(parent) {
+            let index = sibling
+                .and_then(|sibling| parent_node.children.iter().position(|&id| id == sibling))
+                .unwrap_or(parent_node.children.len());
+            parent_node.children.insert(index, child);
+        }
+    }
 }
 
 impl TreeSink for VecSink {
@@ -76,12 +111,7 @@ impl TreeSink for VecSink {
     /// approach provides a good balance between implementation simplicity and performance.
     ///
     /// Reference: https://github.com/servo/html5ever/blob/main/examples/noop-tree-builder.rs
-    fn create_element(
-        &self,
-        name: QualName,
-        attrs: Vec<Attribute>,
-        _flags: ElementFlags,
-    ) -> NodeId {
+    fn create_element(&self, name: QualName, attrs: Vec<Attribute>, flags: ElementFlags) -> NodeId {
         self.with_mut(|dom| {
             let attrs_map = attrs
                 .into_iter()
@@ -101,6 +131,11 @@ impl TreeSink for VecSink {
             // Store the leaked reference for efficient elem_name lookups
             self.element_names.borrow_mut().insert(id, leaked_name);
 
+            if flags.template {
+                let contents = dom.create_without_parent(NodeData::DocumentFragment);
+                self.template_contents.borrow_mut().insert(id, contents);
+            }
+
             id
         })
     }
@@ -111,29 +146,117 @@ impl TreeSink for VecSink {
 
     fn append(&self, parent: &NodeId, child: NodeOrText<NodeId>) {
         self.with_mut(|dom| match child {
-            NodeOrText::AppendNode(id) => {
-                if let Some(node) = dom.node_mut(id) {
-                    node.parent.get_or_insert(*parent);
+            NodeOrText::AppendNode(id) => Self::insert_child(dom, *parent, None, id),
+            NodeOrText::AppendText(t) => {
+                // the tokenizer emits text in chunks (e.g. around entities), merge them
+                // so renderers see the whole run of text
+                let last = dom
+                    .node(*parent)
+                    .and_then(|node| node.children.last().copied());
+                if !Self::merge_text(dom, last, &t) {
+                    dom.create(NodeData::Text(t.to_string()), *parent);
                 }
-                if let Some(parent_node) = dom.node_mut(*parent) {
-                    parent_node.children.push(id);
+            }
+        });
+    }
+
+    fn append_doctype_to_document(&self, _n: StrTendril, _p: StrTendril, _s: StrTendril) {}
+
+    /// Foster parenting: inserts before `element` when it is in the tree (a misplaced table),
+    /// otherwise appends to `prev_element`
+    fn append_based_on_parent_node(
+        &self,
+        element: &NodeId,
+        prev_element: &NodeId,
+        child: NodeOrText<NodeId>,
+    ) {
+        let has_parent = self
+            .dom
+            .borrow()
+            .node(*element)
+            .is_some_and(|node| node.parent.is_some());
+        if has_parent {
+            self.append_before_sibling(element, child);
+        } else {
+            self.append(prev_element, child);
+        }
+    }
+
+    fn append_before_sibling(&self, sibling: &NodeId, child: NodeOrText<NodeId>) {
+        self.with_mut(|dom| {
+            let Some(parent) = dom.node(*sibling).and_then(|node| node.parent) else {
+                return;
+            };
+            let Some(index) = dom
+                .node(parent)
+                .and_then(|node| node.children.iter().position(|id| id == sibling))
+            else {
+                return;
+            };
+            match child {
+                NodeOrText::AppendNode(id) => {
+                    Self::insert_child(dom, parent, Some(*sibling), id);
+                }
+                NodeOrText::AppendText(t) => {
+                    // merged with the text before the sibling, like consecutive appends
+                    let previous = index
+                        .checked_sub(1)
+                        .and_then(|i| dom.node(parent).map(|node| node.children[i]));
+                    if !Self::merge_text(dom, previous, &t) {
+                        let id = dom.create_without_parent(NodeData::Text(t.to_string()));
+                        Self::insert_child(dom, parent, Some(*sibling), id);
+                    }
                 }
             }
-            NodeOrText::AppendText(t) => {
-                dom.create(NodeData::Text(t.to_string()), *parent);
+        });
+    }
+
+    /// Adds the attributes of a misplaced `<html>` or `<body>` to the existing element
+    fn add_attrs_if_missing(&self, target: &NodeId, attrs: Vec<Attribute>) {
+        self.with_mut(|dom| {
+            if let Some(NodeData::Element {
+                attrs: existing, ..
+            }) = dom.node_mut(*target).map(|n| &mut n.data)
+            {
+                for attr in attrs {
+                    existing
+                        .entry(attr.name.local.to_string())
+                        .or_insert_with(|| attr.value.to_string());
+                }
+            }
+        });
+    }
+
+    fn remove_from_parent(&self, target: &NodeId) {
+        self.with_mut(|dom| dom.detach(*target));
+    }
+
+    /// Moves all children of `node` to the end of `new_parent` (adoption agency algorithm)
+    fn reparent_children(&self, node: &NodeId, new_parent: &NodeId) {
+        self.with_mut(|dom| {
+            let children = dom
+                .node_mut(*node)
+                .map(|n| std::mem::take(&mut n.children))
+                .unwrap_or_default();
+            for &child in &children {
+                if let Some(child_node) = dom.node_mut(child) {
+                    child_node.parent = Some(*new_parent);
+                }
+            }
+            if let Some(parent_node) = dom.node_mut(*new_parent) {
+                parent_node.children.extend(children);
             }
         });
     }
 
-    fn append_doctype_to_document(&self, _n: StrTendril, _p: StrTendril, _s: StrTendril) {}
-    fn append_based_on_parent_node(&self, _e: &NodeId, _p: &NodeId, _c: NodeOrText<NodeId>) {}
-    fn append_before_sibling(&self, _s: &NodeId, _c: NodeOrText<NodeId>) {}
-    fn add_attrs_if_missing(&self, _t: &NodeId, _a: Vec<Attribute>) {}
-    fn remove_from_parent(&self, _t: &NodeId) {}
-    fn reparent_children(&self, _n: &NodeId, _np: &NodeId) {}
     fn mark_script_already_started(&self, _n: &NodeId) {}
-    fn get_template_contents(&self, _t: &NodeId) -> NodeId {
-        self.dom.borrow().document
+
+    fn get_template_contents(&self, target: &NodeId) -> NodeId {
+        *self
+            .template_contents
+            .borrow()
+            .get(target)
+            .unwrap_or_else(|| panic!("Node {target} is not a template"))
     }
     fn create_pi(&self, _t: StrTendril, _d: StrTendril) -> NodeId {
         self.get_document()
@@ -143,6 +266,7 @@ impl TreeSink for VecSink {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::*;
 
@@ -157,6 +281,7 @@ mod tests {
     #[case("<div><!-- This is a comment --><p>Content</p></div>", 8)] // div, comment, p, "Content"
     #[case("<div><img src=\"test.jpg\" alt=\"test\"><br><hr></div>", 8)] // div, img, br, hr
     #[case("<p>Simple text</p>", 6)] // p, "Simple text"
+    #[case("<p>a &amp; b &lt; c</p>", 6)] // p, "a & b < c" (merged text chunks)
     #[case("<div><span>Nested</span></div>", 7)] // div, span, "Nested"
     #[case("<h1>Header</h1><p>Paragraph</p>", 8)] // h1, "Header", p, "Paragraph"
     fn test_parse_html_success(#[case] html: &str, #[case] expected_nodes: usize) {
@@ -179,4 +304,213 @@ mod tests {
         let dom = result.unwrap();
         assert_eq!(dom.node_count(), expected_nodes,);
     }
+
+    /// Tree in the format of the html5lib tree-construction tests
+    fn dump_tree(sink: &VecSink) -> String {
+        fn dump(sink: &VecSink, id: NodeId, depth: usize, out: &mut String) {
+            let dom = sink.dom.borrow();
+            let Some(node) = dom.node(id) else {
+                return;
+            };
+            let indent = format!("|{}", " ".repeat(depth * 2 + 1));
+            match &node.data {
+                NodeData::Element { tag, attrs } => {
+                    out.push_str(&format!("{indent}<{}>\n", tag.local));
+                    let mut sorted: Vec<_> = attrs.iter().collect();
+                    sorted.sort();
+                    for (name, value) in sorted {
+                        out.push_str(&format!("{indent}  {name}=\"{value}\"\n"));
+                    }
+                }
+                NodeData::Text(text) => out.push_str(&format!("{indent}\"{text}\"\n")),
+                NodeData::Comment(text) => out.push_str(&format!("{indent}<!-- {text} -->\n")),
+                NodeData::Document | NodeData::DocumentFragment => {}
+            }
+            let children = node.children.clone();
+            drop(dom);
+            let template = sink.template_contents.borrow().get(&id).copied();
+            if let Some(contents) = template {
+                out.push_str(&format!("{indent}  content\n"));
+                dump(sink, contents, depth + 1, out);
+            }
+            for child in children {
+                dump(sink, child, depth + 1, out);
+            }
+        }
+
+        let mut out = String::new();
+        let document = sink.dom.borrow().document;
+        let children = sink.dom.borrow().node(document).unwrap().children.clone();
+        for child in children {
+            dump(sink, child, 0, &mut out);
+        }
+        out
+    }
+
+    /// Malformed HTML, expected trees from the HTML spec and the html5lib tests
+    #[rstest]
+    // adoption agency algorithm for misnested formatting elements
+    #[case(
+        "<b>1<p>2</b>3</p>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |   <body>
+            |     <b>
+            |       "1"
+            |     <p>
+            |       <b>
+            |         "2"
+            |       "3"
+        "#}
+    )]
+    #[case(
+        "<a href=x>1<p>2</a>3</p>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |   <body>
+            |     <a>
+            |       href="x"
+            |       "1"
+            |     <p>
+            |       <a>
+            |         href="x"
+            |         "2"
+            |       "3"
+        "#}
+    )]
+    #[case(
+        "<b>1<i>2</b>3</i>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |   <body>
+            |     <b>
+            |       "1"
+            |       <i>
+            |         "2"
+            |     <i>
+            |       "3"
+        "#}
+    )]
+    // foster parenting of content misplaced in tables
+    #[case(
+        "<table><tr><td>1</td></tr>2</table>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |   <body>
+            |     "2"
+            |     <table>
+            |       <tbody>
+            |         <tr>
+            |           <td>
+            |             "1"
+        "#}
+    )]
+    #[case(
+        "<table><b>1</b><tr><td>2</td></tr></table>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |   <body>
+            |     <b>
+            |       "1"
+            |     <table>
+            |       <tbody>
+            |         <tr>
+            |           <td>
+            |             "2"
+        "#}
+    )]
+    #[case(
+        "<table>A<tr>B</table>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |   <body>
+            |     "AB"
+            |     <table>
+            |       <tbody>
+            |         <tr>
+        "#}
+    )]
+    // template contents are kept apart from the tree
+    #[case(
+        "<template><p>X</p></template><p>Y</p>",
+        indoc! {r#"
+            | <html>
+            |   <head>
+            |     <template>
+            |       content
+            |         <p>
+            |           "X"
+            |   <body>
+            |     <p>
+            |       "Y"
+        "#}
+    )]
+    // attributes of misplaced <html> and <body> are merged
+    #[case(
+        r#"<html lang="en"><body class="a"><html class="x" lang="fr"><body id="b">"#,
+        indoc! {r#"
+            | <html>
+            |   class="x"
+            |   lang="en"
+            |   <head>
+            |   <body>
+            |     class="a"
+            |     id="b"
+        "#}
+    )]
+    fn test_parse_malformed_html(#[case] html: &str, #[case] expected: &str) {
+        let sink = parse_into_sink(html).expect("Failed to parse HTML");
+        assert_eq!(dump_tree(&sink), expected);
+    }
+
+    /// id lookups follow the tree: first match in document order, no detached nodes
+    #[test]
+    fn test_find_element_by_id() {
+        let html = r#"<p id="a">1</p><p id="a">2</p><a name="n">4</a><template><p id="t">3</p></template>"#;
+        let mut dom = parse_html(html).expect("Failed to parse HTML");
+
+        let first = dom.find_element_by_id("a").expect("No element");
+        assert_eq!(dom.collect_text_content(first), "1");
+        assert_eq!(dom.find_element_by_id("t"), None);
+        assert_eq!(dom.find_element_by_id("n"), None);
+        assert!(dom.find_fragment_target("n").is_some());
+
+        dom.detach(first);
+        let second = dom.find_element_by_id("a").expect("No element");
+        assert_eq!(dom.collect_text_content(second), "2");
+    }
+
+    /// nodes moved within the same parent keep their siblings in order
+    #[test]
+    fn test_move_within_parent() {
+        let sink = VecSink {
+            dom: RefCell::new(Dom::new()),
+            element_names: RefCell::new(HashMap::new()),
+            template_contents: RefCell::new(HashMap::new()),
+        };
+        let document = sink.get_document();
+        let [a, b, c] = ["a", "b", "c"].map(|name| {
+            let id = sink.create_comment(StrTendril::from(name));
+            sink.append(&document, NodeOrText::AppendNode(id));
+            id
+        });
+
+        sink.append(&document, NodeOrText::AppendNode(a));
+        assert_eq!(
+            sink.dom.borrow().node(document).unwrap().children,
+            [b, c, a]
+        );
+
+        sink.append_before_sibling(&a, NodeOrText::AppendNode(b));
+        assert_eq!(
+            sink.dom.borrow().node(document).unwrap().children,
+            [c, b, a]
+        );
+    }
 }
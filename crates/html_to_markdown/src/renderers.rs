@@ -1,18 +1,31 @@
 pub mod aside;
+pub mod blockquote;
+pub mod callout;
 pub mod code_block;
+pub mod definition_list;
+pub mod diagram;
+pub mod footnote;
 pub mod generic_block;
 pub mod heading;
+pub mod horizontal_rule;
 pub mod ignored_tags;
 pub mod inline;
 pub mod list;
+pub mod math;
 pub mod media;
 pub mod paragraph;
+pub mod ruby;
 pub mod table;
 
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
-    utils::{cow_to_string, normalize_html_text},
+    options::ConvertOptions,
+    utils::{
+        cow_to_string,
+        escape::{EscapeContext, escape_markdown},
+        normalize_html_text,
+    },
 };
 use std::collections::HashMap;
 use std::default::Default;
@@ -20,14 +33,28 @@ use std::sync::LazyLock;
 
 #[derive(Debug, Default)]
 pub struct Context {
+    /// User options for the output
+    pub options: ConvertOptions,
     pub in_inline: bool,
     /// Depth of nested lists, used for rendering list items
     pub list_depth: usize,
     pub list_first_item: bool,
+    /// Numbering of the innermost ordered list
+    pub list_counter: Option<list::ListCounter>,
     pub in_table: bool,
     pub preserve_whitespace: bool,
+    /// Inside a code span or code block, where text is emitted verbatim
+    pub in_code: bool,
     pub in_heading: bool,
+    /// Offset added to heading levels, computed from the `heading_top_level` option by `Heading::level_offset`
+    pub heading_offset: isize,
+    /// Inside the text of a `[label](url)` link
+    pub in_link_text: bool,
     pub link_info: Option<String>,
+    /// Link targets collected for reference-style links, numbered by position
+    pub link_references: Vec<String>,
+    /// Root element of the rendered content, never left out by the removal rules
+    pub render_root: Option<NodeId>,
     /// Last character of the previous output to determine if block separation is needed
     pub last_char: Option<char>,
 }
@@ -58,18 +85,19 @@ static TAG_RENDERERS: LazyLock<HashMap<&'static str, &'static dyn Renderer>> =
         }
 
         map.insert("p", &paragraph::PARAGRAPH as &'static dyn Renderer);
+        map.insert("ruby", &ruby::RUBY as &'static dyn Renderer);
 
         let inline = &inline::INLINE as &'static dyn Renderer;
-        // <span>, <del>, <ins>, <mark>, <sub>, <sup> and <small> are not transformed to markdown
-        // (text is preserved as is)
+        // <span>, <ins> and <small> are not transformed to markdown (text is preserved as is)
         for tag in [
-            "strong", "b", "em", "i", "span", "br", "del", "ins", "mark", "sub", "sup", "small",
+            "strong", "b", "em", "i", "span", "br", "del", "s", "strike", "ins", "mark", "sub",
+            "sup", "kbd", "abbr", "q", "small",
         ] {
             map.insert(tag, inline);
         }
 
         let media = &media::MEDIA as &'static dyn Renderer;
-        for tag in ["a", "img", "audio", "video"] {
+        for tag in ["a", "img", "picture", "figure", "audio", "video", "iframe"] {
             map.insert(tag, media);
         }
 
@@ -79,7 +107,7 @@ static TAG_RENDERERS: LazyLock<HashMap<&'static str, &'static dyn Renderer>> =
         }
 
         let table = &table::TABLE as &'static dyn Renderer;
-        for tag in ["table", "thead", "tbody", "tr", "th", "td"] {
+        for tag in ["table", "thead", "tbody", "tfoot", "tr", "th", "td"] {
             map.insert(tag, table);
         }
 
@@ -88,7 +116,22 @@ static TAG_RENDERERS: LazyLock<HashMap<&'static str, &'static dyn Renderer>> =
             map.insert(tag, list);
         }
 
+        map.insert(
+            "blockquote",
+            &blockquote::BLOCKQUOTE as &'static dyn Renderer,
+        );
+        map.insert(
+            "hr",
+            &horizontal_rule::HORIZONTAL_RULE as &'static dyn Renderer,
+        );
+
+        let definition_list = &definition_list::DEFINITION_LIST as &'static dyn Renderer;
+        for tag in ["dl", "dt", "dd"] {
+            map.insert(tag, definition_list);
+        }
+
         map.insert("aside", &aside::ASIDE as &'static dyn Renderer);
+        map.insert("details", &callout::CALLOUT as &'static dyn Renderer);
 
         let ignored_tags = &ignored_tags::IGNORED_TAGS as &'static dyn Renderer;
         for tag in ["script", "style", "noscript", "footer", "nav"] {
@@ -101,7 +144,11 @@ static TAG_RENDERERS: LazyLock<HashMap<&'static str, &'static dyn Renderer>> =
 static GENERIC_RENDERERS: LazyLock<Vec<&'static dyn Renderer>> = LazyLock::new(|| {
     // priority order of renderers
     vec![
+        &math::MATH as &'static dyn Renderer, // for KaTeX/MathJax output like <span class="katex">
+        &diagram::DIAGRAM as &'static dyn Renderer, // for diagrams like <div class="mermaid">
+        &footnote::FOOTNOTE as &'static dyn Renderer, // for footnote references and <section class="footnotes">
         &code_block::CODE_BLOCK as &'static dyn Renderer, // for elements like <div class="code-block">
+        &callout::CALLOUT as &'static dyn Renderer, // for admonitions like <div class="admonition note">
         &generic_block::BLOCK as &'static dyn Renderer,
     ]
 });
@@ -111,11 +158,33 @@ pub fn render_node(
     dom: &Dom,
     id: NodeId,
     ctx: &mut Context,
+) -> Result<String, ConvertError> {
+    let output = render_node_output(url, dom, id, ctx)?;
+    // block renderers like <hr> and headings separate themselves from the preceding output
+    if let Some(c) = output.chars().last() {
+        ctx.last_char = Some(c);
+    }
+    Ok(output)
+}
+
+fn render_node_output(
+    url: &str,
+    dom: &Dom,
+    id: NodeId,
+    ctx: &mut Context,
 ) -> Result<String, ConvertError> {
     let Some(node) = dom.node(id) else {
         return Err(ConvertError::InvalidNode(format!("Node {id} not found")));
     };
 
+    // Elements matching the removal rules are left out
+    if matches!(node.data, NodeData::Element { .. })
+        && ctx.render_root != Some(id)
+        && ctx.options.removal_rules.should_remove(dom, id)
+    {
+        return Ok(String::new());
+    }
+
     // Check if the node is an element and has a registered renderer
     if let NodeData::Element { tag, .. } = &node.data
         && let Some(&renderer) = TAG_RENDERERS.get(tag.local.as_ref())
@@ -135,6 +204,60 @@ pub fn render_node(
     render_children(url, dom, id, ctx)
 }
 
+/// Inline elements whose text continues the current line
+const INLINE_TAGS: [&str; 29] = [
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "del", "dfn", "em", "font", "i", "ins",
+    "kbd", "label", "mark", "q", "rb", "rt", "ruby", "s", "samp", "small", "span", "strike",
+    "strong", "sub", "sup",
+];
+
+/// Checks whether a text node is the first content of its block,
+/// i.e. Markdown block markers at its start would be interpreted
+fn is_block_start(dom: &Dom, id: NodeId) -> bool {
+    let mut current = id;
+    loop {
+        let Ok(Some(parent_id)) = dom.get_parent(current) else {
+            return true;
+        };
+        let Ok(mut siblings) = dom.iter_children(parent_id) else {
+            return true;
+        };
+
+        let has_previous_content = siblings
+            .by_ref()
+            .take_while(|&&sibling| sibling != current)
+            .any(|&sibling| match dom.node(sibling).map(|node| &node.data) {
+                Some(NodeData::Text(text)) => !text.trim().is_empty(),
+                Some(NodeData::Element { .. }) => true,
+                _ => false,
+            });
+        if has_previous_content {
+            return false;
+        }
+
+        match dom.get_element_data(parent_id) {
+            Ok((tag, _)) if INLINE_TAGS.contains(&tag.local.as_ref()) => current = parent_id,
+            _ => return true,
+        }
+    }
+}
+
+fn text_escape_context(dom: &Dom, id: NodeId, ctx: &Context) -> EscapeContext {
+    if ctx.in_code {
+        EscapeContext::Code
+    } else if ctx.in_heading {
+        EscapeContext::Heading
+    } else if ctx.in_table {
+        EscapeContext::TableCell
+    } else if ctx.in_link_text {
+        EscapeContext::LinkText
+    } else {
+        EscapeContext::Text {
+            line_start: is_block_start(dom, id),
+        }
+    }
+}
+
 pub fn render_children(
     url: &str,
     dom: &Dom,
@@ -156,8 +279,9 @@ pub fn render_children(
             Ok(result)
         }
         NodeData::Text(text) => {
+            let escape_context = text_escape_context(dom, id, ctx);
             if ctx.preserve_whitespace {
-                Ok(text.clone())
+                Ok(cow_to_string(escape_markdown(text, escape_context)))
                 //Ok(format_list_content(ctx, text))
             } else {
                 let normalized = normalize_html_text(text, ctx.in_inline)
@@ -165,7 +289,7 @@ pub fn render_children(
                     .unwrap_or_default();
 
                 //Ok(format_list_content(ctx, &normalized))
-                Ok(normalized)
+                Ok(cow_to_string(escape_markdown(&normalized, escape_context)))
             }
         }
         NodeData::Document => {
@@ -180,3 +304,47 @@ pub fn render_children(
         _ => Ok(String::new()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Markdown syntax in text nodes is escaped depending on where the text ends up
+    #[rstest]
+    #[case("<p># Not a heading</p>", "\\# Not a heading\n\n")]
+    #[case("<p>1. Not a list</p>", "1\\. Not a list\n\n")]
+    #[case(
+        "<p><strong>Note:</strong> - not a list</p>",
+        "**Note:** - not a list\n\n"
+    )]
+    #[case("<p><span>&gt; not a quote</span></p>", "\\> not a quote\n\n")]
+    #[case(
+        "<p>Use *stars* and [brackets]</p>",
+        "Use \\*stars\\* and \\[brackets\\]\n\n"
+    )]
+    #[case("<p>Write &lt;div&gt; tags</p>", "Write \\<div> tags\n\n")]
+    #[case(
+        r#"<p><a href="https://example.com"># [1] link</a></p>"#,
+        "[# \\[1\\] link](https://example.com)\n\n"
+    )]
+    #[case("<p><code>*raw* [text]</code></p>", "`*raw* [text]`\n\n")]
+    #[case(
+        "<pre><code># comment\n*ptr</code></pre>",
+        "```\n# comment\n*ptr\n```\n\n"
+    )]
+    #[case("<h2>Issue #</h2>", "## Issue \\#\n\n")]
+    #[case(
+        "<table><tr><th>Expr</th></tr><tr><td>a | b</td></tr></table>",
+        "| Expr |\n| --- |\n| a \\| b |\n\n"
+    )]
+    fn test_text_escaping(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result =
+            render_node("", &dom, dom.document, &mut context).expect("Failed to render text");
+        assert_eq!(result, expected);
+    }
+}
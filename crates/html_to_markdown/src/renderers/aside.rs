@@ -1,8 +1,7 @@
-use super::{Context, Renderer, render_children};
+use super::{Context, Renderer, callout, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
-    utils::filtering,
 };
 
 pub struct Aside;
@@ -27,13 +26,9 @@ impl Renderer for Aside {
         id: NodeId,
         ctx: &mut Context,
     ) -> Result<String, ConvertError> {
-        let (_, attrs) = dom.get_element_data(id)?;
-
-        // Check if class should be ignored
-        if let Some(class_value) = attrs.get("class")
-            && filtering::should_ignore_class(class_value)
-        {
-            return Ok(String::new());
+        // Admonition-style asides become Obsidian callouts
+        if callout::CALLOUT.callout_type(dom, id, ctx).is_some() {
+            return callout::CALLOUT.render(url, dom, id, ctx);
         }
 
         // Render children for other aside elements
@@ -61,13 +56,13 @@ mod tests {
     /// Test ignored classes
     #[rstest]
     #[case(r#"<aside class="sidebar">Content</aside>"#, "")]
-    #[case(r#"<aside class="author-info">Author content</aside>"#, "")]
-    #[case(r#"<aside class="publication-meta">Publication content</aside>"#, "")]
-    #[case(r#"<aside class="mobile-nav">Mobile content</aside>"#, "")]
+    #[case(r#"<aside class="author">Author content</aside>"#, "")]
+    #[case(r#"<aside class="publication">Publication content</aside>"#, "")]
+    #[case(r#"<aside class="mobile">Mobile content</aside>"#, "")]
     #[case(r#"<aside class="SIDEBAR">Content</aside>"#, "")] // Case insensitive
     #[case(r#"<aside class="Author">Author content</aside>"#, "")]
-    #[case(r#"<aside class="my-sidebar-widget">Content</aside>"#, "")] // Contains keyword
-    #[case(r#"<aside class="main-author-bio">Content</aside>"#, "")] // Contains keyword
+    #[case(r#"<aside class="my-sidebar-widget">Content</aside>"#, "Content\n\n")] // Whole class names only
+    #[case(r#"<aside class="author-note">Content</aside>"#, "Content\n\n")]
     fn test_ignored_aside_classes(#[case] html: &str, #[case] expected: &str) {
         let dom = parser::parse_html(html).expect("Failed to parse HTML");
         let mut context = Context::default();
@@ -76,20 +71,24 @@ mod tests {
         assert_eq!(result, expected);
     }
 
-    /// Test rendered classes
+    /// Test rendered classes (admonition classes become callouts)
     #[rstest]
     #[case(
         r#"<aside class="note">Important note content</aside>"#,
-        "Important note content\n\n"
+        "> [!note]\n> Important note content\n\n"
     )]
     #[case(
         r#"<aside class="warning">Warning message</aside>"#,
-        "Warning message\n\n"
+        "> [!warning]\n> Warning message\n\n"
     )]
     #[case(
         r#"<aside class="info">Information box</aside>"#,
-        "Information box\n\n"
+        "> [!info]\n> Information box\n\n"
     )]
+    #[case(
+        r#"<aside class="related">Related content</aside>"#,
+        "Related content\n\n"
+    )] // Not an admonition
     #[case(r#"<aside>No class content</aside>"#, "No class content\n\n")] // No class attribute
     fn test_rendered_aside_classes(#[case] html: &str, #[case] expected: &str) {
         let dom = parser::parse_html(html).expect("Failed to parse HTML");
@@ -115,8 +114,14 @@ mod tests {
     /// Test multiple classes
     #[rstest]
     #[case(r#"<aside class="widget sidebar important">Content</aside>"#, "")] // Contains ignored keyword
-    #[case(r#"<aside class="note warning info">Content</aside>"#, "Content\n\n")] // No ignored keywords
-    #[case(r#"<aside class="custom-author-widget">Content</aside>"#, "")] // Contains author
+    #[case(
+        r#"<aside class="note warning info">Content</aside>"#,
+        "> [!warning]\n> Content\n\n"
+    )] // No ignored keywords
+    #[case(
+        r#"<aside class="custom-author-widget">Content</aside>"#,
+        "Content\n\n"
+    )] // Not a whole class name
     fn test_multiple_classes(#[case] html: &str, #[case] expected: &str) {
         let dom = parser::parse_html(html).expect("Failed to parse HTML");
         let mut context = Context::default();
@@ -130,10 +135,11 @@ mod tests {
     #[case(
         r#"<aside class="note"><p>Important information</p><ul><li>Item 1</li><li>Item 2</li></ul></aside>"#,
         indoc! { r#"
-            Important information
-
-            - Item 1
-            - Item 2
+            > [!note]
+            > Important information
+            >
+            > - Item 1
+            > - Item 2
 
             "#
         }
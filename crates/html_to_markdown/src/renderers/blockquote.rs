@@ -0,0 +1,243 @@
+use super::{Context, Renderer, render_node};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+};
+
+/// Represents a blockquote renderer that prefixes every line with "> ".
+/// Nested blockquotes, lists and code blocks are rendered first and then quoted,
+/// so each nesting level adds its own marker.
+pub struct Blockquote;
+
+impl Blockquote {
+    /// Prefixes each line of the rendered content with the quote marker
+    fn quote_lines(content: &str) -> String {
+        content
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the children of a quoted block at list depth 0 and returns the quoted lines.
+    /// Children for which `skip` returns true are left out (e.g. callout titles).
+    pub fn render_quoted(
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+        skip: &dyn Fn(NodeId) -> bool,
+    ) -> Result<String, ConvertError> {
+        let old_list_depth = ctx.list_depth;
+
+        ctx.list_depth = 0;
+        let content = Self::render_filtered_children(url, dom, id, ctx, skip);
+        ctx.list_depth = old_list_depth;
+
+        let content = content?;
+        let trimmed = content.trim_matches('\n').trim_end();
+        if trimmed.trim().is_empty() {
+            return Ok(String::new());
+        }
+        Ok(Self::quote_lines(trimmed))
+    }
+
+    fn render_filtered_children(
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+        skip: &dyn Fn(NodeId) -> bool,
+    ) -> Result<String, ConvertError> {
+        let mut result = String::new();
+        for &child_id in dom.iter_children(id)? {
+            if !skip(child_id) {
+                result.push_str(&render_node(url, dom, child_id, ctx)?);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Places quoted lines into the surrounding block flow (list indentation and separation)
+    pub fn wrap_block(quoted: &str, ctx: &Context) -> String {
+        if ctx.list_depth == 0 {
+            return format!("{quoted}\n\n");
+        }
+
+        let indent = " ".repeat(ctx.list_depth);
+        let indented = quoted
+            .lines()
+            .map(|line| format!("{indent}{line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("\n\n{indented}")
+    }
+}
+
+impl Renderer for Blockquote {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+
+        if let NodeData::Element { tag, .. } = &node.data {
+            tag.local.as_ref() == "blockquote"
+        } else {
+            false
+        }
+    }
+
+    fn render(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let quoted = Self::render_quoted(url, dom, id, ctx, &|_| false)?;
+        if quoted.is_empty() {
+            return Ok(String::new());
+        }
+        Ok(Self::wrap_block(&quoted, ctx))
+    }
+}
+
+pub static BLOCKQUOTE: Blockquote = Blockquote;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// basic blockquote tests
+    #[rstest]
+    #[case("<blockquote>Simple quote</blockquote>", "> Simple quote\n\n")]
+    #[case(
+        "<blockquote><p>Paragraph quote</p></blockquote>",
+        "> Paragraph quote\n\n"
+    )]
+    #[case(
+        "<blockquote><p>First</p><p>Second</p></blockquote>",
+        indoc! {r#"
+            > First
+            >
+            > Second
+
+            "#}
+    )]
+    #[case(
+        "<p>Before</p><blockquote><p>Quoted</p></blockquote><p>After</p>",
+        indoc! {r#"
+            Before
+
+            > Quoted
+
+            After
+
+            "#}
+    )]
+    #[case("<blockquote></blockquote>", "")]
+    #[case("<blockquote><p>  </p></blockquote>", "")]
+    fn test_basic_blockquotes(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render blockquote");
+        assert_eq!(result, expected);
+    }
+
+    /// nested blockquote tests
+    #[rstest]
+    #[case(
+        "<blockquote><p>Outer</p><blockquote><p>Inner</p></blockquote></blockquote>",
+        indoc! {r#"
+            > Outer
+            >
+            > > Inner
+
+            "#}
+    )]
+    #[case(
+        "<blockquote><blockquote><blockquote><p>Deep</p></blockquote></blockquote></blockquote>",
+        "> > > Deep\n\n"
+    )]
+    fn test_nested_blockquotes(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render nested blockquote");
+        assert_eq!(result, expected);
+    }
+
+    /// block content inside blockquotes
+    #[rstest]
+    #[case(
+        "<blockquote><p>Steps:</p><ul><li>One</li><li>Two<ul><li>Nested</li></ul></li></ul></blockquote>",
+        indoc! {r#"
+            > Steps:
+            >
+            > - One
+            > - Two
+            >   - Nested
+
+            "#}
+    )]
+    #[case(
+        r#"<blockquote><pre><code class="language-rust">fn main() {
+    println!("hi");
+}</code></pre></blockquote>"#,
+        indoc! {r#"
+            > ```rust
+            > fn main() {
+            >     println!("hi");
+            > }
+            > ```
+
+            "#}
+    )]
+    #[case(
+        "<blockquote><h2>Title</h2><p>Body with <strong>bold</strong></p></blockquote>",
+        indoc! {r#"
+            > ## Title
+            >
+            > Body with **bold**
+
+            "#}
+    )]
+    fn test_blockquote_with_block_content(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render blockquote");
+        assert_eq!(result, expected);
+    }
+
+    /// blockquotes in lists
+    #[rstest]
+    #[case(
+        "<ul><li>Item<blockquote><p>Quoted in list</p></blockquote></li></ul>",
+        indoc! {r#"
+            - Item
+
+              > Quoted in list
+
+            "#}
+    )]
+    fn test_blockquote_in_list(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render blockquote in list");
+        assert_eq!(result, expected);
+    }
+}
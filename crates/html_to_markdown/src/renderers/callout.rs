@@ -0,0 +1,554 @@
+use super::{Context, Renderer, blockquote::Blockquote, render_children};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+    utils::{cow_to_string, format_list_content, normalize_html_text},
+};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Class tokens that mark an element as an admonition container
+/// (MkDocs / Sphinx, Docusaurus, GitHub, Qiita, Zenn and generic "callout" classes).
+const MARKER_CLASSES: &[&str] = &[
+    "admonition",
+    "theme-admonition",
+    "markdown-alert",
+    "callout",
+    "note",
+    "msg",
+];
+
+/// Prefixes stripped from class tokens before looking up the callout type,
+/// e.g. `admonition-warning`, `theme-admonition-tip`, `alert--danger`.
+const TYPE_PREFIXES: &[&str] = &[
+    "theme-admonition-",
+    "admonition-",
+    "markdown-alert-",
+    "callout-",
+    "alert--",
+    "alert-",
+];
+
+/// Class tokens (or prefixes of them) identifying the title element of an admonition
+const TITLE_CLASSES: &[&str] = &[
+    "admonition-title",
+    "markdown-alert-title",
+    "callout-title",
+    "admonitionheading",
+];
+
+/// Class tokens of purely decorative children (icons, symbols)
+const DECORATION_CLASSES: &[&str] = &["msg-symbol", "admonition-icon", "admonitionicon"];
+
+static DEFAULT_CALLOUT_TYPES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        ("note", "note"),
+        ("seealso", "note"),
+        ("secondary", "note"),
+        ("message", "note"),
+        ("info", "info"),
+        ("todo", "todo"),
+        ("abstract", "abstract"),
+        ("summary", "summary"),
+        ("tldr", "tldr"),
+        ("tip", "tip"),
+        ("hint", "tip"),
+        ("important", "important"),
+        ("success", "success"),
+        ("check", "success"),
+        ("done", "success"),
+        ("question", "question"),
+        ("help", "question"),
+        ("faq", "question"),
+        ("warning", "warning"),
+        ("warn", "warning"),
+        ("attention", "warning"),
+        ("caution", "caution"),
+        ("failure", "failure"),
+        ("fail", "failure"),
+        ("missing", "failure"),
+        ("danger", "danger"),
+        ("alert", "danger"),
+        ("error", "error"),
+        ("bug", "bug"),
+        ("example", "example"),
+        ("quote", "quote"),
+        ("cite", "quote"),
+    ])
+});
+
+/// How `<details>` elements are emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DetailsMode {
+    /// Foldable Obsidian callout (`> [!note]- Summary`)
+    #[default]
+    Callout,
+    /// Keep the element as raw `<details>` HTML
+    Html,
+}
+
+/// Maps admonition class names to Obsidian callout types.
+///
+/// Lookups check user-provided entries first and fall back to the built-in table.
+#[derive(Debug, Clone, Default)]
+pub struct CalloutTypes {
+    overrides: HashMap<String, String>,
+}
+
+impl CalloutTypes {
+    /// Adds or replaces the callout type used for a class name (case-insensitive)
+    pub fn insert(&mut self, class_name: &str, callout_type: &str) -> &mut Self {
+        self.overrides.insert(
+            class_name.to_ascii_lowercase(),
+            callout_type.to_ascii_lowercase(),
+        );
+        self
+    }
+
+    pub fn get(&self, class_name: &str) -> Option<&str> {
+        self.overrides
+            .get(class_name)
+            .map(String::as_str)
+            .or_else(|| DEFAULT_CALLOUT_TYPES.get(class_name).copied())
+    }
+}
+
+/// Represents a callout renderer that converts admonition-style containers
+/// into Obsidian callouts (`> [!warning] Title`).
+/// `<details>` elements become foldable callouts (`> [!note]- Summary`).
+pub struct Callout;
+
+impl Callout {
+    fn class_tokens(dom: &Dom, id: NodeId) -> Vec<String> {
+        dom.get_element_data(id)
+            .ok()
+            .and_then(|(_, attrs)| attrs.get("class"))
+            .map(|class| {
+                class
+                    .split_whitespace()
+                    .map(str::to_ascii_lowercase)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn strip_type_prefix(token: &str) -> &str {
+        TYPE_PREFIXES
+            .iter()
+            .find_map(|prefix| token.strip_prefix(prefix))
+            .unwrap_or(token)
+    }
+
+    fn has_marker_class(tokens: &[String]) -> bool {
+        tokens
+            .iter()
+            .any(|token| MARKER_CLASSES.contains(&token.as_str()))
+    }
+
+    /// Determines the callout type from class tokens, or None if the element is not an admonition
+    pub fn callout_type(&self, dom: &Dom, id: NodeId, ctx: &Context) -> Option<String> {
+        let (tag, _) = dom.get_element_data(id).ok()?;
+        let tokens = Self::class_tokens(dom, id);
+
+        // a specific type wins over the generic marker classes (e.g. "note info" -> info)
+        let mapped = tokens
+            .iter()
+            .filter(|token| !MARKER_CLASSES.contains(&token.as_str()))
+            .find_map(|token| {
+                ctx.options
+                    .callout_types
+                    .get(Self::strip_type_prefix(token))
+            });
+
+        match tag.local.as_ref() {
+            // asides and details are admonitions by nature, a type class is enough
+            "aside" | "details" if mapped.is_some() => mapped.map(str::to_string),
+            "aside" | "details" | "div" | "section" if Self::has_marker_class(&tokens) => Some(
+                mapped
+                    .or_else(|| {
+                        tokens
+                            .iter()
+                            .find_map(|token| ctx.options.callout_types.get(token))
+                    })
+                    .unwrap_or("note")
+                    .to_string(),
+            ),
+            // plain collapsible sections (FAQ entries etc.)
+            "details" => Some("note".to_string()),
+            _ => None,
+        }
+    }
+
+    fn is_title(dom: &Dom, id: NodeId) -> bool {
+        if dom
+            .get_element_data(id)
+            .is_ok_and(|(tag, _)| tag.local.as_ref() == "summary")
+        {
+            return true;
+        }
+        Self::class_tokens(dom, id).iter().any(|token| {
+            TITLE_CLASSES
+                .iter()
+                .any(|title_class| token.starts_with(title_class))
+        })
+    }
+
+    fn is_decoration(dom: &Dom, id: NodeId) -> bool {
+        Self::class_tokens(dom, id).iter().any(|token| {
+            DECORATION_CLASSES
+                .iter()
+                .any(|decoration| token.starts_with(decoration))
+        })
+    }
+
+    fn find_title(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        dom.iter_children(id)
+            .ok()?
+            .copied()
+            .find(|&child_id| Self::is_title(dom, child_id))
+    }
+}
+
+impl Renderer for Callout {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+
+        if let NodeData::Element { tag, .. } = &node.data {
+            match tag.local.as_ref() {
+                "details" => true,
+                "aside" | "div" | "section" => Self::has_marker_class(&Self::class_tokens(dom, id)),
+                _ => false,
+            }
+        } else {
+            false
+        }
+    }
+
+    fn render(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let (tag, attrs) = dom.get_element_data(id)?;
+        let is_details = tag.local.as_ref() == "details";
+        if is_details && ctx.options.details == DetailsMode::Html {
+            return Ok(format_list_content(ctx, &dom.outer_html(id)));
+        }
+
+        let Some(callout_type) = self.callout_type(dom, id, ctx) else {
+            return render_children(url, dom, id, ctx);
+        };
+
+        let fold = if is_details {
+            if attrs.contains_key("open") { "+" } else { "-" }
+        } else {
+            ""
+        };
+
+        let title_id = Self::find_title(dom, id);
+        let body = Blockquote::render_quoted(url, dom, id, ctx, &|child_id| {
+            Some(child_id) == title_id || Self::is_decoration(dom, child_id)
+        })?;
+
+        let title = title_id
+            .map(|title_id| dom.collect_text_content(title_id))
+            .and_then(|text| normalize_html_text(&text, false).map(cow_to_string))
+            // browsers show "Details" when the summary is missing
+            .or_else(|| (is_details && !body.is_empty()).then(|| "Details".to_string()))
+            .unwrap_or_default();
+
+        let mut callout = format!("> [!{callout_type}]{fold}");
+        if !title.is_empty() {
+            callout.push(' ');
+            callout.push_str(&title);
+        }
+        if !body.is_empty() {
+            callout.push('\n');
+            callout.push_str(&body);
+        } else if title.is_empty() {
+            return Ok(String::new());
+        }
+
+        Ok(Blockquote::wrap_block(&callout, ctx))
+    }
+}
+
+pub static CALLOUT: Callout = Callout;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::ConvertOptions;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// admonitions from documentation generators
+    #[rstest]
+    #[case(
+        // MkDocs Material / Sphinx
+        r#"<div class="admonition warning"><p class="admonition-title">Be careful</p><p>This may break.</p></div>"#,
+        indoc! {r#"
+            > [!warning] Be careful
+            > This may break.
+
+            "#}
+    )]
+    #[case(
+        r#"<div class="admonition note"><p class="admonition-title">Note</p><p>First</p><p>Second</p></div>"#,
+        indoc! {r#"
+            > [!note] Note
+            > First
+            >
+            > Second
+
+            "#}
+    )]
+    #[case(
+        r#"<div class="admonition seealso"><p class="admonition-title">See also</p><p>Other docs</p></div>"#,
+        indoc! {r#"
+            > [!note] See also
+            > Other docs
+
+            "#}
+    )]
+    #[case(
+        // Docusaurus v3
+        r#"<div class="theme-admonition theme-admonition-tip admonition_xJq3 alert alert--success"><div class="admonitionHeading_Gvgb"><span class="admonitionIcon_Rf37"><svg></svg></span>tip</div><div class="admonitionContent_BuS1"><p>Use the CLI.</p></div></div>"#,
+        indoc! {r#"
+            > [!tip] tip
+            > Use the CLI.
+
+            "#}
+    )]
+    #[case(
+        // GitHub
+        r#"<div class="markdown-alert markdown-alert-important"><p class="markdown-alert-title">Important</p><p>Read this.</p></div>"#,
+        indoc! {r#"
+            > [!important] Important
+            > Read this.
+
+            "#}
+    )]
+    fn test_documentation_admonitions(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render callout");
+        assert_eq!(result, expected);
+    }
+
+    /// Qiita and Zenn message blocks
+    #[rstest]
+    #[case(
+        r#"<div class="note info"><span class="fa fa-fw fa-check-circle"></span><div><p>Information</p></div></div>"#,
+        "> [!info]\n> Information\n\n"
+    )]
+    #[case(
+        r#"<div class="note warn"><span class="fa fa-fw fa-exclamation-circle"></span><div><p>Warning</p></div></div>"#,
+        "> [!warning]\n> Warning\n\n"
+    )]
+    #[case(
+        r#"<div class="note alert"><div><p>Danger</p></div></div>"#,
+        "> [!danger]\n> Danger\n\n"
+    )]
+    #[case(
+        r#"<aside class="msg message"><div class="msg-symbol">!</div><div class="msg-content"><p>Zenn message</p></div></aside>"#,
+        "> [!note]\n> Zenn message\n\n"
+    )]
+    #[case(
+        r#"<aside class="msg alert"><div class="msg-symbol">!</div><div class="msg-content"><p>Zenn alert</p></div></aside>"#,
+        "> [!danger]\n> Zenn alert\n\n"
+    )]
+    fn test_qiita_zenn_messages(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render callout");
+        assert_eq!(result, expected);
+    }
+
+    /// foldable details-based admonitions
+    #[rstest]
+    #[case(
+        r#"<details class="warning"><summary>Click to expand</summary><p>Hidden text</p></details>"#,
+        indoc! {r#"
+            > [!warning]- Click to expand
+            > Hidden text
+
+            "#}
+    )]
+    #[case(
+        r#"<details class="tip" open><summary>Opened</summary><p>Visible text</p></details>"#,
+        indoc! {r#"
+            > [!tip]+ Opened
+            > Visible text
+
+            "#}
+    )]
+    #[case(
+        r#"<details><summary>How do I reset my password?</summary><p>Open the settings page.</p><p>Click <b>Reset</b>.</p></details>"#,
+        indoc! {r#"
+            > [!note]- How do I reset my password?
+            > Open the settings page.
+            >
+            > Click **Reset**.
+
+            "#}
+    )]
+    #[case(
+        r#"<details open><summary>Plain</summary>Body</details>"#,
+        "> [!note]+ Plain\n> Body\n\n"
+    )]
+    #[case(
+        r#"<details><p>No summary</p></details>"#,
+        "> [!note]- Details\n> No summary\n\n"
+    )]
+    #[case(
+        r#"<details><summary>Only a title</summary></details>"#,
+        "> [!note]- Only a title\n\n"
+    )]
+    #[case(r#"<details></details>"#, "")]
+    #[case(
+        r#"<details><summary>Outer</summary><p>Text</p><details><summary>Inner</summary><p>Nested</p></details></details>"#,
+        indoc! {r#"
+            > [!note]- Outer
+            > Text
+            >
+            > > [!note]- Inner
+            > > Nested
+
+            "#}
+    )]
+    #[case(
+        r#"<ul><li>FAQ<details><summary>Question</summary><p>Answer</p></details></li></ul>"#,
+        indoc! {r#"
+            - FAQ
+
+              > [!note]- Question
+              > Answer
+
+            "#}
+    )]
+    fn test_foldable_admonitions(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render callout");
+        assert_eq!(result, expected);
+    }
+
+    /// callouts with nested content and in other blocks
+    #[rstest]
+    #[case(
+        r#"<div class="admonition example"><p class="admonition-title">Steps</p><ul><li>One</li><li>Two</li></ul><pre><code class="language-bash">make</code></pre></div>"#,
+        indoc! {r#"
+            > [!example] Steps
+            > - One
+            > - Two
+            >
+            > ```bash
+            > make
+            > ```
+
+            "#}
+    )]
+    #[case(
+        r#"<ul><li>Item<div class="admonition note"><p>Inside list</p></div></li></ul>"#,
+        indoc! {r#"
+            - Item
+
+              > [!note]
+              > Inside list
+
+            "#}
+    )]
+    #[case(r#"<div class="admonition note"></div>"#, "")]
+    fn test_callout_nested_content(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render callout");
+        assert_eq!(result, expected);
+    }
+
+    /// elements that are not admonitions
+    #[rstest]
+    #[case(r#"<div class="warning">Plain div</div>"#, "Plain div")]
+    #[case(r#"<div class="content"><p>Text</p></div>"#, "Text\n\n")]
+    fn test_non_admonitions(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render element");
+        assert_eq!(result, expected);
+    }
+
+    /// <details> kept as raw HTML
+    #[rstest]
+    #[case(
+        r#"<details><summary>Question</summary><p>Answer</p></details>"#,
+        "<details><summary>Question</summary><p>Answer</p></details>\n\n"
+    )]
+    #[case(
+        r#"<details class="warning" open><summary>Careful</summary>Text</details>"#,
+        "<details class=\"warning\" open=\"\"><summary>Careful</summary>Text</details>\n\n"
+    )]
+    #[case(
+        r#"<div class="admonition note"><p>Not details</p></div>"#,
+        "> [!note]\n> Not details\n\n"
+    )]
+    fn test_details_html_mode(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().details(DetailsMode::Html),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render details");
+        assert_eq!(result, expected);
+    }
+
+    /// custom class to callout type mapping
+    #[rstest]
+    #[case(
+        r#"<div class="admonition custom-box"><p>Custom</p></div>"#,
+        "> [!quote]\n> Custom\n\n"
+    )]
+    #[case(
+        r#"<div class="admonition warning"><p>Overridden</p></div>"#,
+        "> [!danger]\n> Overridden\n\n"
+    )]
+    #[case(
+        r#"<aside class="custom-box">Aside</aside>"#,
+        "> [!quote]\n> Aside\n\n"
+    )]
+    fn test_custom_callout_types(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default()
+                .callout_type("custom-box", "quote")
+                .callout_type("warning", "danger"),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render callout");
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("admonition-warning", "warning")]
+    #[case("theme-admonition-caution", "caution")]
+    #[case("alert--danger", "danger")]
+    #[case("markdown-alert-note", "note")]
+    #[case("warning", "warning")]
+    fn test_strip_type_prefix(#[case] token: &str, #[case] expected: &str) {
+        assert_eq!(Callout::strip_type_prefix(token), expected);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Context, Renderer, render_children};
+use super::{Context, Renderer, diagram::DIAGRAM, math, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
@@ -61,21 +61,86 @@ static SUPPORTED_LANGUAGES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
         "dockerfile",
         "sql",
         "graphql",
+        "mermaid",
+        "plantuml",
+        "graphviz",
+        "dot",
     ])
 });
 
+/// Fence character used for code blocks
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FenceStyle {
+    /// ```` ``` ````
+    #[default]
+    Backtick,
+    /// `~~~`
+    Tilde,
+}
+
+impl FenceStyle {
+    fn fence_char(self) -> char {
+        match self {
+            Self::Backtick => '`',
+            Self::Tilde => '~',
+        }
+    }
+}
+
+/// Length of the longest run of `target` in `content`
+fn longest_run(content: &str, target: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in content.chars() {
+        if c == target {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
 /// Represents a code block renderer that handles <pre>, <code>.
 /// Also handles the element with attribute data-lang or class="code-frame".
 pub struct CodeBlock;
 
 impl CodeBlock {
-    fn create_code_block(
+    /// Fence longer than any run of the fence character inside the code (at least 3)
+    fn fence(content: &str, style: FenceStyle) -> String {
+        let fence_char = style.fence_char();
+        let length = (longest_run(content, fence_char) + 1).max(3);
+        fence_char.to_string().repeat(length)
+    }
+
+    /// Wraps inline code in enough backticks to contain the backticks inside it
+    fn create_inline_code(content: &str) -> String {
+        let delimiter = "`".repeat(longest_run(content, '`') + 1);
+
+        // a leading/trailing backtick would merge with the delimiter, and a single space
+        // on both sides is stripped by Markdown parsers
+        let needs_padding = content.starts_with('`')
+            || content.ends_with('`')
+            || (content.starts_with(' ') && content.ends_with(' ') && !content.trim().is_empty());
+        let padding = if needs_padding { " " } else { "" };
+
+        format!("{delimiter}{padding}{content}{padding}{delimiter}")
+    }
+
+    pub fn create_code_block(
         &self,
         content: &str,
         language: Option<String>,
         ctx: &mut Context,
     ) -> String {
+        // Qiita and markdown-it render $$ blocks as code with the "math" language
+        if language.as_deref() == Some("math") {
+            return math::format_math(content, true, ctx);
+        }
+
         let indent = " ".repeat(ctx.list_depth);
+        let fence = Self::fence(content, ctx.options.code_fence);
         let capacity = content.len() + language.as_ref().map_or(0, |l| l.len()) + 10; // "```", newlines, etc.
 
         let mut result = String::with_capacity(capacity);
@@ -83,7 +148,7 @@ impl CodeBlock {
             result.push_str("\n\n");
         }
 
-        result.push_str(&format!("{indent}```"));
+        result.push_str(&format!("{indent}{fence}"));
         if let Some(lang) = &language {
             result.push_str(lang);
         }
@@ -92,7 +157,7 @@ impl CodeBlock {
         if !content.ends_with('\n') {
             result.push('\n');
         }
-        result.push_str(&format!("{indent}```"));
+        result.push_str(&format!("{indent}{fence}"));
 
         if ctx.list_depth == 0 {
             result.push_str("\n\n");
@@ -202,9 +267,12 @@ impl CodeBlock {
         ctx: &mut Context,
     ) -> Result<String, ConvertError> {
         let old_preserve = ctx.preserve_whitespace;
+        let old_in_code = ctx.in_code;
         ctx.preserve_whitespace = true;
+        ctx.in_code = true;
         let content = render_children(url, dom, id, ctx)?;
         ctx.preserve_whitespace = old_preserve;
+        ctx.in_code = old_in_code;
         Ok(content)
     }
 
@@ -231,7 +299,8 @@ impl Renderer for CodeBlock {
         };
 
         match tag.local.as_ref() {
-            "pre" => true,
+            // <pre class="mermaid"> is rendered by the diagram renderer
+            "pre" => !DIAGRAM.matches(dom, id),
             "code" => {
                 // <code> in <pre> should not be rendered as inline code
                 if let Ok(Some(parent_id)) = dom.get_parent(id)
@@ -270,7 +339,7 @@ impl Renderer for CodeBlock {
             "code" => {
                 // inline code
                 let content = self.render_with_preserved_whitespace(url, dom, id, ctx)?;
-                Ok(format!("`{content}`"))
+                Ok(Self::create_inline_code(&content))
             }
             _ => render_children(url, dom, id, ctx),
         }
@@ -282,6 +351,7 @@ pub static CODE_BLOCK: CodeBlock = CodeBlock;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::ConvertOptions;
     use crate::parser;
     use crate::renderers;
     use indoc::indoc;
@@ -555,6 +625,56 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    /// inline code containing backticks
+    #[rstest]
+    #[case("<code>a`b</code>", "``a`b``")]
+    #[case("<code>echo ``nested``</code>", "``` echo ``nested`` ```")]
+    #[case("<code>`tick</code>", "`` `tick ``")]
+    #[case("<code>tick`</code>", "`` tick` ``")]
+    #[case("<code>` `</code>", "`` ` ` ``")]
+    #[case("<code> padded </code>", "`  padded  `")]
+    #[case("<code>   </code>", "`   `")]
+    fn test_inline_code_with_backticks(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render inline code");
+        assert_eq!(result, expected);
+    }
+
+    /// fence length follows the longest run of fence characters in the content
+    #[rstest]
+    #[case(
+        "<pre><code>Use ``` to start a block</code></pre>",
+        FenceStyle::Backtick,
+        "````\nUse ``` to start a block\n````\n\n"
+    )]
+    #[case(
+        "<pre><code>`````\ncode\n`````</code></pre>",
+        FenceStyle::Backtick,
+        "``````\n`````\ncode\n`````\n``````\n\n"
+    )]
+    #[case(
+        "<pre><code class=\"language-markdown\">```rust\nfn main() {}\n```</code></pre>",
+        FenceStyle::Tilde,
+        "~~~markdown\n```rust\nfn main() {}\n```\n~~~\n\n"
+    )]
+    #[case(
+        "<pre><code>~~~~\ntext</code></pre>",
+        FenceStyle::Tilde,
+        "~~~~~\n~~~~\ntext\n~~~~~\n\n"
+    )]
+    fn test_fence_length(#[case] html: &str, #[case] style: FenceStyle, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().code_fence(style),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render code block");
+        assert_eq!(result, expected);
+    }
+
     /// invalid language names
     #[rstest]
     #[case("rust", true)]
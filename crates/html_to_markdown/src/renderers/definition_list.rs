@@ -0,0 +1,301 @@
+use super::{Context, Renderer, render_children};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+    utils::format_list_content,
+};
+
+/// Indentation for continuation lines of a definition (Pandoc / PHP Markdown Extra style)
+const DEFINITION_INDENT: &str = "    ";
+
+/// A group of terms followed by their definitions
+#[derive(Debug, Default)]
+struct DefinitionGroup {
+    terms: Vec<String>,
+    definitions: Vec<String>,
+}
+
+/// Represents a definition list renderer that handles <dl>, <dt> and <dd>.
+///
+/// Output uses the widely supported extended syntax:
+///
+/// ```text
+/// **Term**
+/// : Definition
+/// ```
+pub struct DefinitionList;
+
+impl DefinitionList {
+    /// Collects <dt>/<dd> elements, looking through <div> wrappers allowed inside <dl>
+    fn collect_items(dom: &Dom, id: NodeId, items: &mut Vec<NodeId>) {
+        let Ok(children) = dom.iter_children(id) else {
+            return;
+        };
+
+        for &child_id in children {
+            let Ok((tag, _)) = dom.get_element_data(child_id) else {
+                continue;
+            };
+            match tag.local.as_ref() {
+                "dt" | "dd" => items.push(child_id),
+                "div" => Self::collect_items(dom, child_id, items),
+                _ => {}
+            }
+        }
+    }
+
+    fn render_block_content(
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let old_list_depth = ctx.list_depth;
+        ctx.list_depth = 0;
+        let content = render_children(url, dom, id, ctx);
+        ctx.list_depth = old_list_depth;
+        Ok(content?.trim().to_string())
+    }
+
+    /// Terms are bold, unless they already have bold parts (`<dt><b>Bold</b> term</dt>`),
+    /// which would break the emphasis when wrapped again
+    fn format_term(dom: &Dom, id: NodeId, content: &str) -> String {
+        let single_line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        let has_bold = ["b", "strong"]
+            .iter()
+            .any(|tag| dom.find_element_by_tag(id, tag).is_some());
+        if has_bold {
+            single_line
+        } else {
+            format!("**{single_line}**")
+        }
+    }
+
+    fn format_definition(content: &str) -> String {
+        let mut lines = content.lines();
+        let mut result = format!(": {}", lines.next().unwrap_or_default());
+        for line in lines {
+            result.push('\n');
+            if !line.is_empty() {
+                result.push_str(DEFINITION_INDENT);
+                result.push_str(line);
+            }
+        }
+        result
+    }
+
+    fn render_definition_list(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let mut items = Vec::new();
+        Self::collect_items(dom, id, &mut items);
+
+        let mut groups: Vec<DefinitionGroup> = Vec::new();
+        for item_id in items {
+            let (tag, _) = dom.get_element_data(item_id)?;
+            let content = Self::render_block_content(url, dom, item_id, ctx)?;
+            if content.is_empty() {
+                continue;
+            }
+
+            match tag.local.as_ref() {
+                "dt" => {
+                    // a term after definitions starts a new group
+                    if groups
+                        .last()
+                        .is_none_or(|group| !group.definitions.is_empty())
+                    {
+                        groups.push(DefinitionGroup::default());
+                    }
+                    if let Some(group) = groups.last_mut() {
+                        group.terms.push(Self::format_term(dom, item_id, &content));
+                    }
+                }
+                _ => {
+                    if groups.is_empty() {
+                        groups.push(DefinitionGroup::default());
+                    }
+                    if let Some(group) = groups.last_mut() {
+                        group.definitions.push(Self::format_definition(&content));
+                    }
+                }
+            }
+        }
+
+        let rendered = groups
+            .iter()
+            .map(|group| {
+                group
+                    .terms
+                    .iter()
+                    .chain(group.definitions.iter())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        if rendered.is_empty() {
+            return Ok(String::new());
+        }
+
+        if ctx.list_depth == 0 {
+            return Ok(format!("{rendered}\n\n"));
+        }
+        let indent = " ".repeat(ctx.list_depth);
+        let indented = rendered
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{indent}{line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(format!("\n\n{indented}"))
+    }
+}
+
+impl Renderer for DefinitionList {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+
+        if let NodeData::Element { tag, .. } = &node.data {
+            matches!(tag.local.as_ref(), "dl" | "dt" | "dd")
+        } else {
+            false
+        }
+    }
+
+    fn render(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let (tag, _) = dom.get_element_data(id)?;
+
+        match tag.local.as_ref() {
+            "dl" => self.render_definition_list(url, dom, id, ctx),
+            // <dt>/<dd> outside of <dl> are rendered as plain paragraphs
+            _ => {
+                let content = render_children(url, dom, id, ctx)?;
+                if content.trim().is_empty() {
+                    return Ok(String::new());
+                }
+                Ok(format_list_content(ctx, content.trim()))
+            }
+        }
+    }
+}
+
+pub static DEFINITION_LIST: DefinitionList = DefinitionList;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// basic definition list tests
+    #[rstest]
+    #[case(
+        "<dl><dt>HTML</dt><dd>HyperText Markup Language</dd></dl>",
+        indoc! {r#"
+            **HTML**
+            : HyperText Markup Language
+
+            "#}
+    )]
+    #[case(
+        "<dl><dt>Rust</dt><dd>A language</dd><dt>Cargo</dt><dd>A build tool</dd><dd>A package manager</dd></dl>",
+        indoc! {r#"
+            **Rust**
+            : A language
+
+            **Cargo**
+            : A build tool
+            : A package manager
+
+            "#}
+    )]
+    #[case(
+        "<dl><dt>colour</dt><dt>color</dt><dd>Visual property</dd></dl>",
+        indoc! {r#"
+            **colour**
+            **color**
+            : Visual property
+
+            "#}
+    )]
+    #[case(
+        "<dl><div><dt>Grouped</dt><dd>Inside div</dd></div></dl>",
+        indoc! {r#"
+            **Grouped**
+            : Inside div
+
+            "#}
+    )]
+    #[case("<dl></dl>", "")]
+    #[case("<dl><dt> </dt><dd></dd></dl>", "")]
+    fn test_definition_lists(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render definition list");
+        assert_eq!(result, expected);
+    }
+
+    /// formatted and block content in definitions
+    #[rstest]
+    #[case(
+        "<dl><dt><strong>Bold term</strong></dt><dd>Has <em>emphasis</em></dd></dl>",
+        indoc! {r#"
+            **Bold term**
+            : Has *emphasis*
+
+            "#}
+    )]
+    #[case(
+        "<dl><dt><b>Bold</b> term</dt><dd>Partly bold</dd><dt>**Literal** stars</dt><dd>Escaped</dd></dl>",
+        indoc! {r#"
+            **Bold** term
+            : Partly bold
+
+            **\*\*Literal\*\* stars**
+            : Escaped
+
+            "#}
+    )]
+    #[case(
+        "<dl><dt>Options</dt><dd><p>Choose one:</p><ul><li>A</li><li>B</li></ul></dd></dl>",
+        indoc! {r#"
+            **Options**
+            : Choose one:
+
+                - A
+                - B
+
+            "#}
+    )]
+    fn test_definition_list_content(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render definition list");
+        assert_eq!(result, expected);
+    }
+}
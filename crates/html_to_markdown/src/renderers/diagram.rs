@@ -0,0 +1,276 @@
+use super::{Context, Renderer, code_block::CODE_BLOCK};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+    utils::format_list_content,
+};
+
+/// Diagram languages and the code block language Obsidian (and its plugins) expect
+const DIAGRAM_TYPES: [(&str, &str); 5] = [
+    ("mermaid", "mermaid"),
+    ("plantuml", "plantuml"),
+    ("puml", "plantuml"),
+    ("graphviz", "dot"),
+    ("dot", "dot"),
+];
+
+/// Attributes keeping the diagram source after it was rendered to SVG
+const SOURCE_ATTRIBUTES: [&str; 3] = ["data-diagram-source", "data-plain", "data-source"];
+
+/// Attributes naming the diagram type
+const TYPE_ATTRIBUTES: [&str; 4] = ["data-diagram-type", "data-diagram", "data-type", "lang"];
+
+/// Represents a diagram renderer for Mermaid, PlantUML and Graphviz containers
+/// (`<pre class="mermaid">`, `<div class="mermaid">`, `data-diagram-source`).
+///
+/// The diagram source is emitted as a code block so the diagram stays live in Obsidian.
+pub struct Diagram;
+
+impl Diagram {
+    fn diagram_language(value: &str) -> Option<&'static str> {
+        let value = value.trim().to_ascii_lowercase();
+        DIAGRAM_TYPES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|&(_, language)| language)
+    }
+
+    /// Diagram language from the class or type attributes of the element
+    fn element_language(dom: &Dom, id: NodeId) -> Option<&'static str> {
+        let (_, attrs) = dom.get_element_data(id).ok()?;
+
+        // "dot" is too common as a class name (carousel indicators, status dots)
+        let from_class = attrs.get("class").and_then(|class| {
+            class
+                .split_whitespace()
+                .filter(|&name| name != "dot")
+                .find_map(Self::diagram_language)
+        });
+
+        from_class.or_else(|| {
+            TYPE_ATTRIBUTES
+                .iter()
+                .filter_map(|name| attrs.get(*name))
+                .find_map(|value| Self::diagram_language(value))
+        })
+    }
+
+    fn has_source_attribute(dom: &Dom, id: NodeId) -> bool {
+        dom.get_element_data(id)
+            .is_ok_and(|(_, attrs)| attrs.contains_key("data-diagram-source"))
+    }
+
+    /// Finds the diagram source kept in an attribute of the element or its descendants
+    fn find_source_attribute(dom: &Dom, id: NodeId) -> Option<String> {
+        if let Ok((_, attrs)) = dom.get_element_data(id)
+            && let Some(source) = SOURCE_ATTRIBUTES
+                .iter()
+                .filter_map(|name| attrs.get(*name))
+                .find(|source| !source.trim().is_empty())
+        {
+            return Some(source.clone());
+        }
+
+        dom.iter_children(id)
+            .ok()?
+            .find_map(|&child_id| Self::find_source_attribute(dom, child_id))
+    }
+
+    fn find_svg(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        dom.find_element_by_tag(id, "svg")
+    }
+
+    /// Removes the common indentation of the source lines
+    fn dedent(source: &str) -> String {
+        let lines: Vec<&str> = source.trim_matches('\n').lines().collect();
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        lines
+            .iter()
+            .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_string()
+    }
+}
+
+impl Renderer for Diagram {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+        let NodeData::Element { tag, .. } = &node.data else {
+            return false;
+        };
+
+        // <code class="language-mermaid"> is a regular code block with a language
+        if tag.local.as_ref() == "code" {
+            return false;
+        }
+        Self::has_source_attribute(dom, id) || Self::element_language(dom, id).is_some()
+    }
+
+    fn render(
+        &self,
+        _url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let language = Self::element_language(dom, id).unwrap_or("mermaid");
+
+        let source = match Self::find_source_attribute(dom, id) {
+            Some(source) => Some(source),
+            // the source is still the text content until the diagram is rendered
+            None if Self::find_svg(dom, id).is_none() => Some(dom.collect_text_content(id)),
+            None => None,
+        };
+
+        match source.map(|source| Self::dedent(&source)) {
+            Some(source) if !source.is_empty() => {
+                Ok(CODE_BLOCK.create_code_block(&source, Some(language.to_string()), ctx))
+            }
+            Some(_) => Ok(String::new()),
+            // only the rendered SVG is left, keep it as HTML which Obsidian displays
+            None => {
+                let svg = Self::find_svg(dom, id).map(|svg| dom.outer_html(svg));
+                Ok(svg.map_or_else(String::new, |svg| format_list_content(ctx, &svg)))
+            }
+        }
+    }
+}
+
+pub static DIAGRAM: Diagram = Diagram;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// diagram containers with source text
+    #[rstest]
+    #[case(
+        r#"<pre class="mermaid">graph TD
+    A --&gt; B</pre>"#,
+        indoc! {r#"
+            ```mermaid
+            graph TD
+                A --> B
+            ```
+
+            "#}
+    )]
+    #[case(
+        r#"<div class="mermaid">
+            sequenceDiagram
+                Alice->>Bob: Hello
+        </div>"#,
+        indoc! {r#"
+            ```mermaid
+            sequenceDiagram
+                Alice->>Bob: Hello
+            ```
+
+            "#}
+    )]
+    #[case(
+        r#"<div class="plantuml">@startuml
+Alice -> Bob
+@enduml</div>"#,
+        indoc! {r#"
+            ```plantuml
+            @startuml
+            Alice -> Bob
+            @enduml
+            ```
+
+            "#}
+    )]
+    #[case(
+        r#"<div class="graphviz">digraph { a -&gt; b }</div>"#,
+        "```dot\ndigraph { a -> b }\n```\n\n"
+    )]
+    #[case(
+        r#"<pre lang="mermaid">pie
+    "A" : 1</pre>"#,
+        "```mermaid\npie\n    \"A\" : 1\n```\n\n"
+    )]
+    #[case(r#"<div class="mermaid">   </div>"#, "")]
+    #[case(r#"<p>Step<span class="dot">•</span>two</p>"#, "Step•two\n\n")]
+    fn test_diagram_source_text(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render diagram");
+        assert_eq!(result, expected);
+    }
+
+    /// pre-rendered diagrams
+    #[rstest]
+    #[case(
+        r#"<div class="mermaid" data-diagram-source="graph LR
+  A --&gt; B" data-processed="true"><svg id="mermaid-1"><g><text>A</text><text>B</text></g></svg></div>"#,
+        "```mermaid\ngraph LR\n  A --> B\n```\n\n"
+    )]
+    #[case(
+        r#"<figure data-diagram-source="flowchart TD; X--&gt;Y"><svg><text>X</text></svg></figure>"#,
+        "```mermaid\nflowchart TD; X-->Y\n```\n\n"
+    )]
+    #[case(
+        r#"<section class="js-render-needs-enrichment" data-type="mermaid"><div class="js-render-enrichment-target" data-plain="graph TD
+  A--&gt;B"><pre lang="mermaid" aria-label="Raw mermaid code">graph TD
+  A--&gt;B</pre></div></section>"#,
+        "```mermaid\ngraph TD\n  A-->B\n```\n\n"
+    )]
+    #[case(
+        r#"<pre class="mermaid" data-processed="true"><svg viewBox="0 0 10 10"><text>A</text></svg></pre>"#,
+        "<svg viewBox=\"0 0 10 10\"><text>A</text></svg>\n\n"
+    )]
+    fn test_rendered_diagrams(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render diagram");
+        assert_eq!(result, expected);
+    }
+
+    /// diagrams inside lists and regular code blocks with diagram languages
+    #[rstest]
+    #[case(
+        r#"<ul><li>Flow:<div class="mermaid">graph TD; A--&gt;B</div></li></ul>"#,
+        indoc! {r#"
+            - Flow:
+
+              ```mermaid
+              graph TD; A-->B
+              ```
+
+            "#}
+    )]
+    #[case(
+        r#"<pre><code class="language-mermaid">graph TD</code></pre>"#,
+        "```mermaid\ngraph TD\n```\n\n"
+    )]
+    #[case(
+        r#"<div class="code-frame" data-lang="plantuml"><div class="highlight"><pre><code>@startuml
+@enduml</code></pre></div></div>"#,
+        "```plantuml\n@startuml\n@enduml\n```\n\n"
+    )]
+    fn test_diagram_code_blocks(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render diagram");
+        assert_eq!(result, expected);
+    }
+}
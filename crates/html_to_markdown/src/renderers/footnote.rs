@@ -0,0 +1,413 @@
+use super::{Context, Renderer, render_children};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+};
+
+/// Class tokens of the elements holding footnote definitions
+/// (markdown-it / Zenn, GitHub, kramdown, pandoc, Wikipedia, Hatena Blog).
+const CONTAINER_CLASSES: &[&str] = &[
+    "footnotes",
+    "footnotes-list",
+    "footnote",
+    "footnote-list",
+    "references",
+];
+
+/// Roles of the elements holding footnote definitions
+const CONTAINER_ROLES: &[&str] = &["doc-endnotes", "doc-footnotes"];
+
+/// Class tokens of links and wrappers pointing back to the footnote reference
+const BACKLINK_CLASSES: &[&str] = &[
+    "footnote-backref",
+    "data-footnote-backref",
+    "reversefootnote",
+    "footnote-back",
+    "mw-cite-backlink",
+];
+
+/// Represents a footnote renderer.
+///
+/// Reference links (`<sup><a href="#fn1">1</a></sup>`) become `[^1]` and the
+/// definition list becomes `[^1]: text` lines. Backlinks to the reference are removed.
+/// Labels are the position of the definition in its list, so references and
+/// definitions always agree.
+pub struct Footnote;
+
+impl Footnote {
+    fn tag_name(dom: &Dom, id: NodeId) -> Option<&str> {
+        dom.get_element_data(id)
+            .ok()
+            .map(|(tag, _)| tag.local.as_ref())
+    }
+
+    fn attr<'a>(dom: &'a Dom, id: NodeId, name: &str) -> Option<&'a str> {
+        dom.get_element_data(id)
+            .ok()
+            .and_then(|(_, attrs)| attrs.get(name))
+            .map(String::as_str)
+    }
+
+    fn has_class(dom: &Dom, id: NodeId, classes: &[&str]) -> bool {
+        Self::attr(dom, id, "class").is_some_and(|class| {
+            class
+                .split_whitespace()
+                .any(|token| classes.contains(&token.to_ascii_lowercase().as_str()))
+        })
+    }
+
+    fn parent(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        dom.get_parent(id).ok().flatten()
+    }
+
+    fn element_children(dom: &Dom, id: NodeId) -> Vec<NodeId> {
+        dom.iter_children(id)
+            .map(|children| {
+                children
+                    .copied()
+                    .filter(|&child_id| dom.get_element_data(child_id).is_ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Element (section, div, ol) marked as the footnote section
+    fn is_container(dom: &Dom, id: NodeId) -> bool {
+        Self::has_class(dom, id, CONTAINER_CLASSES)
+            || Self::attr(dom, id, "data-footnotes").is_some()
+            || Self::attr(dom, id, "role").is_some_and(|role| CONTAINER_ROLES.contains(&role))
+    }
+
+    /// List whose items are footnote definitions
+    fn is_footnote_list(dom: &Dom, id: NodeId) -> bool {
+        if !matches!(Self::tag_name(dom, id), Some("ol" | "ul")) {
+            return false;
+        }
+        Self::is_container(dom, id)
+            || Self::parent(dom, id).is_some_and(|parent_id| Self::is_container(dom, parent_id))
+            || Self::element_children(dom, id)
+                .into_iter()
+                .any(|child_id| Self::attr(dom, child_id, "role") == Some("doc-endnote"))
+    }
+
+    /// `<li>` of a footnote list, or a `<p class="footnote">` in `<div class="footnote">` (Hatena Blog)
+    fn is_definition(dom: &Dom, id: NodeId) -> bool {
+        let Some(parent_id) = Self::parent(dom, id) else {
+            return false;
+        };
+        match Self::tag_name(dom, id) {
+            Some("li") => Self::is_footnote_list(dom, parent_id),
+            Some("p") => {
+                Self::has_class(dom, id, &["footnote"])
+                    && Self::has_class(dom, parent_id, &["footnote"])
+            }
+            _ => false,
+        }
+    }
+
+    fn collect_definitions(dom: &Dom, id: NodeId, definitions: &mut Vec<NodeId>) {
+        for child_id in Self::element_children(dom, id) {
+            if Self::is_definition(dom, child_id) {
+                definitions.push(child_id);
+            } else {
+                Self::collect_definitions(dom, child_id, definitions);
+            }
+        }
+    }
+
+    /// Element targeted by a `#fragment` link, matched by `id` or by `<a name>`
+    fn link_target(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        let fragment = Self::attr(dom, id, "href")?.trim().strip_prefix('#')?;
+        if fragment.is_empty() {
+            return None;
+        }
+        dom.find_fragment_target(fragment)
+    }
+
+    /// Footnote definition referenced by a link
+    fn referenced_definition(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        if Self::tag_name(dom, id) != Some("a") {
+            return None;
+        }
+        let target_id = Self::link_target(dom, id)?;
+        if Self::is_definition(dom, target_id) {
+            return Some(target_id);
+        }
+        // the target is an anchor inside the definition (Hatena Blog)
+        Self::parent(dom, target_id).filter(|&parent_id| Self::is_definition(dom, parent_id))
+    }
+
+    /// Single reference link wrapped in `<sup>` (text outside the link is not allowed)
+    fn wrapped_reference(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        let mut link = None;
+        for &child_id in dom.iter_children(id).ok()? {
+            match dom.node(child_id).map(|node| &node.data) {
+                Some(NodeData::Element { .. }) if link.is_none() => link = Some(child_id),
+                Some(NodeData::Text(text)) if text.trim().is_empty() => {}
+                Some(NodeData::Comment(_)) => {}
+                _ => return None,
+            }
+        }
+        link.filter(|&link_id| Self::referenced_definition(dom, link_id).is_some())
+    }
+
+    fn is_reference(dom: &Dom, id: NodeId) -> bool {
+        Self::referenced_definition(dom, id).is_some()
+            || (Self::tag_name(dom, id) == Some("sup")
+                && Self::wrapped_reference(dom, id).is_some())
+    }
+
+    /// Link back from a definition to its reference
+    fn is_backlink(dom: &Dom, id: NodeId) -> bool {
+        if Self::has_class(dom, id, BACKLINK_CLASSES)
+            || Self::attr(dom, id, "data-footnote-backref").is_some()
+            || Self::attr(dom, id, "role") == Some("doc-backlink")
+        {
+            return true;
+        }
+        if Self::tag_name(dom, id) != Some("a") {
+            return false;
+        }
+        // the reference is the link itself or its <sup> wrapper
+        Self::link_target(dom, id).is_some_and(|target_id| {
+            Self::is_reference(dom, target_id)
+                || Self::element_children(dom, target_id)
+                    .into_iter()
+                    .any(|child_id| Self::is_reference(dom, child_id))
+        })
+    }
+
+    /// Label of a definition, its 1-based position in the list (honoring `<ol start>`)
+    fn label(dom: &Dom, definition_id: NodeId) -> String {
+        let Some(parent_id) = Self::parent(dom, definition_id) else {
+            return "1".to_string();
+        };
+        let tag = Self::tag_name(dom, definition_id);
+        let start = Self::attr(dom, parent_id, "start")
+            .and_then(|start| start.trim().parse::<i64>().ok())
+            .unwrap_or(1);
+        let position = Self::element_children(dom, parent_id)
+            .into_iter()
+            .filter(|&child_id| Self::tag_name(dom, child_id) == tag)
+            .position(|child_id| child_id == definition_id)
+            .unwrap_or(0);
+        (start + position as i64).to_string()
+    }
+
+    fn render_definition(
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let old_list_depth = ctx.list_depth;
+        ctx.list_depth = 0;
+        let content = render_children(url, dom, id, ctx);
+        ctx.list_depth = old_list_depth;
+
+        // the delimiter after a removed footnote number ("*1: text") is left at the start
+        let content = content?;
+        let content = content.trim().trim_start_matches([':', ' ']);
+
+        // continuation paragraphs are indented to stay in the footnote
+        let body = content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                if i == 0 || line.trim().is_empty() {
+                    line.trim_end().to_string()
+                } else {
+                    format!("    {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let label = Self::label(dom, id);
+        if body.is_empty() {
+            Ok(format!("[^{label}]:"))
+        } else {
+            Ok(format!("[^{label}]: {body}"))
+        }
+    }
+}
+
+impl Renderer for Footnote {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        match Self::tag_name(dom, id) {
+            Some("a") => Self::is_reference(dom, id) || Self::is_backlink(dom, id),
+            Some("sup") => Self::is_reference(dom, id),
+            Some("span") => Self::is_backlink(dom, id),
+            Some("ol" | "ul") => Self::is_footnote_list(dom, id),
+            Some("section" | "div" | "aside") => {
+                if !Self::is_container(dom, id) {
+                    return false;
+                }
+                let mut definitions = Vec::new();
+                Self::collect_definitions(dom, id, &mut definitions);
+                !definitions.is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    fn render(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let reference = match Self::tag_name(dom, id) {
+            Some("sup") => Self::wrapped_reference(dom, id),
+            Some("a") => Some(id),
+            _ => None,
+        };
+        if let Some(definition_id) =
+            reference.and_then(|link_id| Self::referenced_definition(dom, link_id))
+        {
+            ctx.last_char = Some(']');
+            return Ok(format!("[^{}]", Self::label(dom, definition_id)));
+        }
+        if matches!(Self::tag_name(dom, id), Some("a" | "span")) {
+            // backlink
+            return Ok(String::new());
+        }
+
+        // headings and separators of the section are dropped,
+        // Obsidian renders its own footnote section
+        let mut definitions = Vec::new();
+        Self::collect_definitions(dom, id, &mut definitions);
+        let rendered = definitions
+            .into_iter()
+            .map(|definition_id| Self::render_definition(url, dom, definition_id, ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if rendered.is_empty() {
+            return Ok(String::new());
+        }
+        Ok(format!("{}\n\n", rendered.join("\n")))
+    }
+}
+
+pub static FOOTNOTE: Footnote = Footnote;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// footnotes generated by Markdown processors
+    #[rstest]
+    #[case(
+        // markdown-it / Zenn
+        r##"<p>Rust is fast<sup class="footnote-ref"><a href="#fn1" id="fnref1">[1]</a></sup>.</p><section class="footnotes"><ol class="footnotes-list"><li id="fn1" class="footnote-item"><p>Benchmarks vary. <a href="#fnref1" class="footnote-backref">↩︎</a></p></li></ol></section>"##,
+        indoc! {r#"
+            Rust is fast[^1].
+
+            [^1]: Benchmarks vary.
+
+            "#}
+    )]
+    #[case(
+        // GitHub
+        r##"<p>Text<sup><a href="#user-content-fn-note" id="user-content-fnref-note" data-footnote-ref>1</a></sup> and more<sup><a href="#user-content-fn-2" id="user-content-fnref-2" data-footnote-ref>2</a></sup></p>
+<section data-footnotes class="footnotes"><h2 id="footnote-label" class="sr-only">Footnotes</h2>
+<ol><li id="user-content-fn-note"><p>Named note <a href="#user-content-fnref-note" data-footnote-backref class="data-footnote-backref">↩</a></p></li>
+<li id="user-content-fn-2"><p>Second <a href="#user-content-fnref-2" data-footnote-backref>↩</a></p></li></ol></section>"##,
+        indoc! {r#"
+            Text[^1] and more[^2]
+
+            [^1]: Named note
+            [^2]: Second
+
+            "#}
+    )]
+    #[case(
+        // kramdown
+        r##"<p>Claim<sup id="fnref:1" role="doc-noteref"><a href="#fn:1" class="footnote" rel="footnote">1</a></sup></p>
+<div class="footnotes" role="doc-endnotes"><ol><li id="fn:1" role="doc-endnote"><p>Source <a href="#fnref:1" class="reversefootnote" role="doc-backlink">&#8617;</a></p></li></ol></div>"##,
+        "Claim[^1]\n\n[^1]: Source\n\n"
+    )]
+    #[case(
+        // pandoc
+        r##"<p>Note<a href="#fn1" class="footnote-ref" id="fnref1" role="doc-noteref"><sup>1</sup></a></p>
+<section id="footnotes" class="footnotes footnotes-end-of-document" role="doc-endnotes"><hr><ol><li id="fn1"><p>Pandoc note<a href="#fnref1" class="footnote-back" role="doc-backlink">↩︎</a></p></li></ol></section>"##,
+        "Note[^1]\n\n[^1]: Pandoc note\n\n"
+    )]
+    fn test_markdown_footnotes(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render footnotes");
+        assert_eq!(result, expected);
+    }
+
+    /// footnotes of wikis and blog services
+    #[rstest]
+    #[case(
+        // Wikipedia, with a named reference used twice
+        r##"<p>Tokyo is large.<sup id="cite_ref-pop_1-0" class="reference"><a href="#cite_note-pop-1">[1]</a></sup> It is old.<sup id="cite_ref-2" class="reference"><a href="#cite_note-2">[2]</a></sup><sup id="cite_ref-pop_1-1" class="reference"><a href="#cite_note-pop-1">[1]</a></sup></p>
+<div class="reflist"><div class="mw-references-wrap"><ol class="references">
+<li id="cite_note-pop-1"><span class="mw-cite-backlink">^ <a href="#cite_ref-pop_1-0"><sup><i><b>a</b></i></sup></a> <a href="#cite_ref-pop_1-1"><sup><i><b>b</b></i></sup></a></span> <span class="reference-text">Census 2020.</span></li>
+<li id="cite_note-2"><span class="mw-cite-backlink"><b><a href="#cite_ref-2">^</a></b></span> <span class="reference-text"><a href="https://example.com/history">History</a></span></li>
+</ol></div></div>"##,
+        indoc! {r#"
+            Tokyo is large.[^1] It is old.[^2][^1]
+
+            [^1]: Census 2020.
+            [^2]: [History](https://example.com/history)
+
+            "#}
+    )]
+    #[case(
+        // Hatena Blog
+        r##"<p>Footnote<a href="#f-1a2b" id="fn-1a2b" name="fn-1a2b" title="Hatena note">*1</a> here</p>
+<div class="footnote"><p class="footnote"><a href="#fn-1a2b" id="f-1a2b" name="f-1a2b" class="footnote-number">*1</a><span class="footnote-delimiter">:</span><span class="footnote-text">Hatena note</span></p></div>"##,
+        "Footnote[^1] here\n\n[^1]: Hatena note\n\n"
+    )]
+    fn test_site_footnotes(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render footnotes");
+        assert_eq!(result, expected);
+    }
+
+    /// definitions with block content and links that are not footnotes
+    #[rstest]
+    #[case(
+        r##"<p>A<sup><a href="#fn1">1</a></sup></p><section class="footnotes"><ol><li id="fn1"><p>First paragraph.</p><p>Second paragraph. <a href="#fnref1" class="footnote-backref">↩</a></p></li></ol></section>"##,
+        indoc! {r#"
+            A[^1]
+
+            [^1]: First paragraph.
+
+                Second paragraph.
+
+            "#}
+    )]
+    #[case(
+        r##"<p>B<sup><a href="#fn3">3</a></sup></p><ol class="footnotes" start="3"><li id="fn3">Third</li></ol>"##,
+        "B[^3]\n\n[^3]: Third\n\n"
+    )]
+    #[case(
+        r##"<h2 id="intro">Intro</h2><p><a href="#intro">Intro</a> and <sup><a href="#missing">1</a></sup></p>"##,
+        "## Intro\n\n[[#Intro|Intro]] and <sup>1</sup>\n\n"
+    )]
+    #[case(
+        r##"<ol class="steps"><li id="fn1">Not a footnote</li></ol>"##,
+        "1. Not a footnote\n\n"
+    )]
+    fn test_footnote_edge_cases(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render footnotes");
+        assert_eq!(result, expected);
+    }
+}
@@ -2,7 +2,6 @@ use super::{Context, Renderer, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
-    utils::filtering,
 };
 
 pub struct GenericBlock;
@@ -30,16 +29,9 @@ impl Renderer for GenericBlock {
         id: NodeId,
         ctx: &mut Context,
     ) -> Result<String, ConvertError> {
-        let (tag, attrs) = dom.get_element_data(id)?;
+        let (tag, _) = dom.get_element_data(id)?;
 
         if tag.local.as_ref() == "div" {
-            // Check if class should be ignored
-            if let Some(class_value) = attrs.get("class")
-                && filtering::should_ignore_class(class_value)
-            {
-                return Ok(String::new());
-            }
-
             // div elements are treated transparently - just render children without any formatting
             return render_children(url, dom, id, ctx);
         }
@@ -1,23 +1,179 @@
-use super::{Context, Renderer, render_children};
+use super::{Context, Renderer, media::Media, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
     utils::{cow_to_string, normalize_heading_content},
 };
 
+const HEADING_TAGS: [&str; 6] = ["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Class tokens of permalink anchors added to headings by static site generators
+/// (GitHub, Sphinx, markdown-it-anchor, Docusaurus, AnchorJS, Zola)
+const PERMALINK_CLASSES: &[&str] = &[
+    "anchor",
+    "headerlink",
+    "header-anchor",
+    "heading-anchor",
+    "anchor-link",
+    "hash-link",
+    "anchorjs-link",
+    "permalink",
+    "zola-anchor",
+];
+
+/// Text of permalink anchors
+const PERMALINK_SYMBOLS: &[&str] = &["#", "¶", "§", "🔗", "∞"];
+
+/// Characters Obsidian does not allow in the heading part of a link target
+const LINK_TARGET_FORBIDDEN: &[char] = &['#', '|', '^', ':', '[', ']', '\\'];
+
 pub struct Heading;
 
 impl Heading {
-    fn get_heading_level(tag_name: &str) -> &'static str {
+    fn get_heading_level(tag_name: &str) -> usize {
         match tag_name {
-            "h1" => "#",
-            "h2" => "##",
-            "h3" => "###",
-            "h4" => "####",
-            "h5" => "#####",
-            "h6" => "######",
-            _ => "#",
+            "h2" => 2,
+            "h3" => 3,
+            "h4" => 4,
+            "h5" => 5,
+            "h6" => 6,
+            _ => 1,
+        }
+    }
+
+    /// Offset that moves the highest heading under `root` to `top_level`
+    pub fn level_offset(dom: &Dom, root: NodeId, top_level: usize) -> isize {
+        let highest = HEADING_TAGS
+            .iter()
+            .find(|tag| dom.find_element_by_tag(root, tag).is_some())
+            .map(|tag| Self::get_heading_level(tag));
+
+        highest.map_or(0, |highest| top_level as isize - highest as isize)
+    }
+
+    fn enclosing_heading(dom: &Dom, id: NodeId) -> Option<NodeId> {
+        let mut current = dom.get_parent(id).ok().flatten();
+        while let Some(node_id) = current {
+            if Self::is_heading(dom, node_id) {
+                return Some(node_id);
+            }
+            current = dom.get_parent(node_id).ok().flatten();
+        }
+        None
+    }
+
+    fn visible_text(dom: &Dom, id: NodeId) -> String {
+        dom.collect_text_content(id)
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\u{200B}')
+            .collect()
+    }
+
+    /// Checks whether a link is a permalink anchor of its heading (`<a class="anchor" href="#id">#</a>`).
+    /// Links whose text is the whole heading and image links are kept.
+    pub fn is_permalink(dom: &Dom, id: NodeId) -> bool {
+        let Ok((tag, attrs)) = dom.get_element_data(id) else {
+            return false;
+        };
+        if tag.local.as_ref() != "a"
+            || !attrs
+                .get("href")
+                .is_some_and(|href| href.trim().starts_with('#'))
+        {
+            return false;
+        }
+
+        let text = Self::visible_text(dom, id);
+        if PERMALINK_SYMBOLS.contains(&text.as_str()) {
+            return true;
+        }
+
+        let has_permalink_class = attrs.get("class").is_some_and(|class| {
+            class
+                .split_whitespace()
+                .any(|token| PERMALINK_CLASSES.contains(&token))
+        }) || attrs.get("aria-hidden").is_some_and(|v| v == "true");
+        if text.is_empty() {
+            // an icon (<svg>) without text, but not an image link
+            let has_elements = dom.iter_children(id).is_ok_and(|mut children| {
+                children.any(|&child| {
+                    dom.node(child)
+                        .is_some_and(|node| matches!(node.data, NodeData::Element { .. }))
+                })
+            });
+            return dom.find_element_by_tag(id, "img").is_none()
+                && (has_permalink_class || !has_elements);
+        }
+
+        has_permalink_class
+            && Self::enclosing_heading(dom, id)
+                .is_some_and(|heading_id| Self::visible_text(dom, heading_id).len() > text.len())
+    }
+
+    fn is_heading(dom: &Dom, id: NodeId) -> bool {
+        dom.get_element_data(id)
+            .is_ok_and(|(tag, _)| HEADING_TAGS.contains(&tag.local.as_ref()))
+    }
+
+    /// Heading targeted by an in-page fragment: the heading with the `id`,
+    /// or the heading around an anchor like `<h2><a id="intro"></a>Intro</h2>`
+    fn fragment_target(dom: &Dom, fragment: &str) -> Option<NodeId> {
+        let target = dom.find_fragment_target(fragment)?;
+
+        if Self::is_heading(dom, target) {
+            Some(target)
+        } else {
+            Self::enclosing_heading(dom, target)
+        }
+    }
+
+    /// Text of a heading without its permalink anchors
+    fn collect_heading_text(dom: &Dom, id: NodeId, text: &mut String) {
+        let Some(node) = dom.node(id) else {
+            return;
+        };
+        match &node.data {
+            NodeData::Text(content) => text.push_str(content),
+            NodeData::Element { .. } if !Self::is_permalink(dom, id) => {
+                for &child_id in &node.children {
+                    Self::collect_heading_text(dom, child_id, text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Obsidian link target (`#Heading text`) for an in-page `href="#fragment"`,
+    /// none when no heading matches the fragment
+    pub fn link_target(dom: &Dom, fragment: &str) -> Option<String> {
+        if fragment.is_empty() {
+            return None;
+        }
+        let heading_id = Self::fragment_target(dom, fragment)?;
+
+        let mut text = String::new();
+        Self::collect_heading_text(dom, heading_id, &mut text);
+        let text = text
+            .replace(LINK_TARGET_FORBIDDEN, " ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        (!text.is_empty()).then(|| format!("#{text}"))
+    }
+
+    /// Obsidian block id (`^block-id`) from the heading `id`, only letters, digits and dashes are allowed
+    fn block_id(id: &str) -> Option<String> {
+        let mut block_id = String::with_capacity(id.len());
+        for c in id.chars() {
+            if c.is_alphanumeric() {
+                block_id.push(c);
+            } else if !block_id.is_empty() && !block_id.ends_with('-') {
+                block_id.push('-');
+            }
         }
+        let block_id = block_id.trim_end_matches('-');
+        (!block_id.is_empty()).then(|| block_id.to_string())
     }
 
     fn render_with_context(
@@ -45,7 +201,8 @@ impl Heading {
         &self,
         content: &str,
         level: &str,
-        link_url: &Option<String>,
+        link_destination: Option<String>,
+        block_id: Option<&str>,
         needs_separation: bool,
     ) -> String {
         let trimmed = content.trim();
@@ -54,10 +211,13 @@ impl Heading {
         }
 
         let newlines = if needs_separation { "\n\n" } else { "" };
+        let block_id = block_id.map_or_else(String::new, |block_id| format!(" ^{block_id}"));
 
-        match link_url {
-            Some(url) => format!("{newlines}{level} [{trimmed}]({url})\n\n"),
-            None => format!("{newlines}{level} {trimmed}\n\n"),
+        match link_destination {
+            Some(destination) => {
+                format!("{newlines}{level} [{trimmed}]{destination}{block_id}\n\n")
+            }
+            None => format!("{newlines}{level} {trimmed}{block_id}\n\n"),
         }
     }
 }
@@ -89,13 +249,29 @@ impl Renderer for Heading {
             return Ok(String::new());
         }
 
-        let (tag, _) = dom.get_element_data(id)?;
-        let tag_name = tag.local.as_ref();
-        let level = Self::get_heading_level(tag_name);
+        let (tag, attrs) = dom.get_element_data(id)?;
+        let level = Self::get_heading_level(tag.local.as_ref()) as isize + ctx.heading_offset;
+        let level = "#".repeat(level.clamp(1, 6) as usize);
+
+        let block_id = attrs
+            .get("id")
+            .filter(|_| ctx.options.heading_block_ids)
+            .and_then(|id| Self::block_id(id));
+
+        let link_destination = ctx
+            .link_info
+            .clone()
+            .map(|link| Media::link_destination(ctx, &link));
 
         let needs_separation = old_last_char.is_some_and(|c| c != '\n');
 
-        Ok(self.format_heading(&content, level, &ctx.link_info, needs_separation))
+        Ok(self.format_heading(
+            &content,
+            &level,
+            link_destination,
+            block_id.as_deref(),
+            needs_separation,
+        ))
     }
 }
 
@@ -104,6 +280,7 @@ pub static HEADING: Heading = Heading;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::ConvertOptions;
     use crate::parser;
     use crate::renderers;
     use pretty_assertions::assert_eq;
@@ -213,6 +390,111 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    /// permalink anchors added by static site generators
+    #[rstest]
+    #[case(
+        // GitHub
+        r##"<h2 id="install"><a class="anchor" aria-hidden="true" href="#install"><svg class="octicon octicon-link"><path d="M0"></path></svg></a>Install</h2>"##,
+        "## Install\n\n"
+    )]
+    #[case(
+        // Sphinx
+        r##"<h2>Usage<a class="headerlink" href="#usage" title="Permalink to this heading">¶</a></h2>"##,
+        "## Usage\n\n"
+    )]
+    #[case(
+        // markdown-it-anchor / VuePress
+        r##"<h2 id="setup"><a class="header-anchor" href="#setup">#</a> Setup</h2>"##,
+        "## Setup\n\n"
+    )]
+    #[case(
+        // Docusaurus
+        r##"<h3 id="api">API<a href="#api" class="hash-link" aria-label="Direct link to API" title="Direct link to API">&#8203;</a></h3>"##,
+        "### API\n\n"
+    )]
+    #[case(
+        r##"<h2><a class="anchor" href="#title">Linked Title</a></h2>"##,
+        "## Linked Title\n\n"
+    )]
+    #[case(
+        r##"<h2>Read <a href="https://example.com/#faq">the FAQ</a></h2>"##,
+        "## Read [the FAQ](https://example.com/#faq)\n\n"
+    )]
+    // image links and links to other pages are not permalinks
+    #[case(
+        r##"<h2 id="gallery"><a href="#gallery"><img src="https://example.com/pic.png" alt="Pic"></a> Title</h2>"##,
+        "## Pic Title\n\n"
+    )]
+    #[case(
+        r##"<h2><a class="anchor" href="https://example.com/page#x"><img src="https://example.com/logo.png" alt="Logo"></a> Title</h2>"##,
+        "## [Logo](https://example.com/page#x) Title\n\n"
+    )]
+    #[case(
+        r##"<h2>Title<a href="https://example.com/page#x">¶</a></h2>"##,
+        "## Title[¶](https://example.com/page#x)\n\n"
+    )]
+    fn test_headings_with_permalinks(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render heading");
+        assert_eq!(result, expected);
+    }
+
+    /// heading level shift so the highest heading gets the top level
+    #[rstest]
+    #[case(
+        "<h1>Title</h1><h2>Section</h2><h3>Detail</h3>",
+        2,
+        "## Title\n\n### Section\n\n#### Detail\n\n"
+    )]
+    #[case("<h3>Section</h3><h4>Detail</h4>", 2, "## Section\n\n### Detail\n\n")]
+    #[case("<h1>One</h1><h1>Two</h1>", 2, "## One\n\n## Two\n\n")]
+    #[case("<h1>Top</h1><h6>Deepest</h6>", 2, "## Top\n\n###### Deepest\n\n")]
+    #[case("<h2>Kept</h2>", 2, "## Kept\n\n")]
+    #[case("<p>No headings</p>", 2, "No headings\n\n")]
+    fn test_heading_level_shift(
+        #[case] html: &str,
+        #[case] top_level: usize,
+        #[case] expected: &str,
+    ) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            heading_offset: Heading::level_offset(&dom, dom.document, top_level),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render heading");
+        assert_eq!(result, expected);
+    }
+
+    /// block ids from heading ids
+    #[rstest]
+    #[case(
+        r#"<h2 id="getting-started">Getting Started</h2>"#,
+        "## Getting Started ^getting-started\n\n"
+    )]
+    #[case(
+        r#"<h2 id="user_content.Setup Guide">Setup</h2>"#,
+        "## Setup ^user-content-Setup-Guide\n\n"
+    )]
+    #[case(r#"<h2 id="__">Symbols only</h2>"#, "## Symbols only\n\n")]
+    #[case(r#"<h2>No id</h2>"#, "## No id\n\n")]
+    #[case(
+        r##"<h2 id="usage">Usage<a class="headerlink" href="#usage">¶</a></h2>"##,
+        "## Usage ^usage\n\n"
+    )]
+    fn test_heading_block_ids(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().heading_block_ids(true),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render heading");
+        assert_eq!(result, expected);
+    }
+
     /// nested and complex structures in headings
     #[rstest]
     #[case(
@@ -237,7 +519,7 @@ mod tests {
 
     /// HTML entities and special characters in headings
     #[rstest]
-    #[case("<h1>Using &lt;script&gt; tags</h1>", "# Using <script> tags\n\n")]
+    #[case("<h1>Using &lt;script&gt; tags</h1>", "# Using \\<script> tags\n\n")]
     #[case("<h1>Math: 2 &gt; 1 &amp; 1 &lt; 2</h1>", "# Math: 2 > 1 & 1 < 2\n\n")]
     #[case(
         "<h1>Quotes: &quot;Hello&quot; &amp; 'World'</h1>",
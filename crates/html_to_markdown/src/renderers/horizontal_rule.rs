@@ -0,0 +1,93 @@
+use super::{Context, Renderer};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+    utils::format_list_content,
+};
+
+/// Represents a horizontal rule renderer that converts <hr> into "---".
+pub struct HorizontalRule;
+
+impl Renderer for HorizontalRule {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+
+        if let NodeData::Element { tag, .. } = &node.data {
+            tag.local.as_ref() == "hr"
+        } else {
+            false
+        }
+    }
+
+    fn render(
+        &self,
+        _url: &str,
+        _dom: &Dom,
+        _id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        // "---" directly below a line of text would turn it into a setext heading
+        let needs_separation = ctx.last_char.is_some_and(|c| c != '\n');
+        let newlines = if needs_separation && ctx.list_depth == 0 {
+            "\n\n"
+        } else {
+            ""
+        };
+
+        Ok(format!("{newlines}{}", format_list_content(ctx, "---")))
+    }
+}
+
+pub static HORIZONTAL_RULE: HorizontalRule = HorizontalRule;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("<hr>", "---\n\n")]
+    #[case(
+        "<p>Section 1</p><hr><p>Section 2</p>",
+        indoc! {r#"
+            Section 1
+
+            ---
+
+            Section 2
+
+            "#}
+    )]
+    #[case("<span>inline</span><hr>", "inline\n\n---\n\n")]
+    // separated from text ending right before the rule, not from finished blocks
+    #[case("<div>text only<hr>after</div>", "text only\n\n---\n\nafter")]
+    #[case("<p>Some <strong>bold</strong></p><hr>", "Some **bold**\n\n---\n\n")]
+    #[case(
+        r##"<p>Claim<sup id="r1"><a href="#fn1">1</a></sup></p><hr><ol class="footnotes"><li id="fn1">Source</li></ol>"##,
+        "Claim[^1]\n\n---\n\n[^1]: Source\n\n"
+    )]
+    #[case(
+        "<ul><li><p>Item</p><hr><p>More</p></li></ul>",
+        indoc! {r#"
+            - Item
+
+              ---
+
+              More
+
+            "#}
+    )]
+    fn test_horizontal_rule(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render horizontal rule");
+        assert_eq!(result, expected);
+    }
+}
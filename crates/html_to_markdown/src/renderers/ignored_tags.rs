@@ -1,4 +1,4 @@
-use super::{Context, Renderer};
+use super::{Context, Renderer, math::Math, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
@@ -12,6 +12,11 @@ impl Renderer for IgnoredTags {
             return false;
         };
 
+        // <script type="math/tex"> holds MathJax source and is rendered as math
+        if Math::is_tex_script(dom, id) {
+            return false;
+        }
+
         if let NodeData::Element { tag, .. } = &node.data {
             matches!(
                 tag.local.as_ref(),
@@ -25,11 +30,15 @@ impl Renderer for IgnoredTags {
 
     fn render(
         &self,
-        _url: &str,
-        _dom: &Dom,
-        _id: NodeId,
-        _ctx: &mut Context,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
     ) -> Result<String, ConvertError> {
+        let (tag, _) = dom.get_element_data(id)?;
+        if !ctx.options.skip_nav_footer && matches!(tag.local.as_ref(), "footer" | "nav") {
+            return render_children(url, dom, id, ctx);
+        }
         Ok(String::new())
     }
 }
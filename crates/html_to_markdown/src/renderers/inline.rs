@@ -1,9 +1,36 @@
-use super::{Context, Renderer, render_children};
+use super::{Context, Renderer, footnote::FOOTNOTE, math::MATH, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
 };
 
+/// How inline elements without a Markdown equivalent (<sub>, <sup>, <kbd>, <abbr>) are emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum InlineHtml {
+    /// Keep the element as inline HTML, which Obsidian renders
+    #[default]
+    Preserve,
+    /// Emit only the text content
+    TextOnly,
+}
+
+/// Delimiter of emphasis (`*em*`) and strong emphasis (`**strong**`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum EmphasisMarker {
+    #[default]
+    Asterisk,
+    Underscore,
+}
+
+impl EmphasisMarker {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Asterisk => "*",
+            Self::Underscore => "_",
+        }
+    }
+}
+
 pub struct Inline;
 
 impl Inline {
@@ -11,6 +38,70 @@ impl Inline {
     fn update_last_char(&self, ctx: &mut Context, content: &str) {
         ctx.last_char = content.chars().last();
     }
+
+    fn render_inline_children(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let old_inline_status = ctx.in_inline;
+        ctx.in_inline = true;
+        let content = render_children(url, dom, id, ctx);
+        ctx.in_inline = old_inline_status;
+        content
+    }
+
+    /// Wraps content with a delimiter, keeping edge spaces outside of it
+    /// (`~~ x ~~` would not be parsed as strikethrough)
+    fn wrap_delimited(content: &str, delimiter: &str) -> String {
+        let trimmed = content.trim();
+        if trimmed.is_empty() {
+            return content.to_string();
+        }
+
+        let leading = if content.starts_with(char::is_whitespace) {
+            " "
+        } else {
+            ""
+        };
+        let trailing = if content.ends_with(char::is_whitespace) {
+            " "
+        } else {
+            ""
+        };
+        format!("{leading}{delimiter}{trimmed}{delimiter}{trailing}")
+    }
+
+    fn wrap_html(content: &str, tag: &str, title: Option<&str>, mode: InlineHtml) -> String {
+        if content.trim().is_empty() || mode == InlineHtml::TextOnly {
+            return content.to_string();
+        }
+
+        match title {
+            Some(title) => {
+                let escaped = title.replace('&', "&amp;").replace('"', "&quot;");
+                format!("<{tag} title=\"{escaped}\">{content}</{tag}>")
+            }
+            None => format!("<{tag}>{content}</{tag}>"),
+        }
+    }
+
+    /// Nested <q> elements alternate between double and single quotes
+    fn is_nested_quote(dom: &Dom, id: NodeId) -> bool {
+        let mut current = dom.get_parent(id).ok().flatten();
+        while let Some(parent_id) = current {
+            if dom
+                .get_element_data(parent_id)
+                .is_ok_and(|(tag, _)| tag.local.as_ref() == "q")
+            {
+                return true;
+            }
+            current = dom.get_parent(parent_id).ok().flatten();
+        }
+        false
+    }
 }
 
 impl Renderer for Inline {
@@ -20,9 +111,34 @@ impl Renderer for Inline {
         };
 
         if let NodeData::Element { tag, .. } = &node.data {
+            // KaTeX and MathJax output is made of spans
+            if tag.local.as_ref() == "span" && MATH.matches(dom, id) {
+                return false;
+            }
+            // footnote references (<sup><a href="#fn1">) and backlinks
+            if matches!(tag.local.as_ref(), "sup" | "span") && FOOTNOTE.matches(dom, id) {
+                return false;
+            }
+
             matches!(
                 tag.local.as_ref(),
-                "strong" | "b" | "em" | "i" | "span" | "br"
+                "strong"
+                    | "b"
+                    | "em"
+                    | "i"
+                    | "span"
+                    | "br"
+                    | "del"
+                    | "s"
+                    | "strike"
+                    | "ins"
+                    | "mark"
+                    | "sub"
+                    | "sup"
+                    | "kbd"
+                    | "abbr"
+                    | "q"
+                    | "small"
             )
         } else {
             false
@@ -36,29 +152,55 @@ impl Renderer for Inline {
         id: NodeId,
         ctx: &mut Context,
     ) -> Result<String, ConvertError> {
-        let (tag, _) = dom.get_element_data(id)?;
-        let old_inline_status = ctx.in_inline;
+        let (tag, attrs) = dom.get_element_data(id)?;
+        let tag_name = tag.local.as_ref();
 
-        let result = match tag.local.as_ref() {
+        let result = match tag_name {
             "strong" | "b" => {
-                ctx.in_inline = true;
-                let content = render_children(url, dom, id, ctx)?;
-                ctx.in_inline = old_inline_status;
-                format!("**{content}**")
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                let marker = ctx.options.emphasis_marker.as_str();
+                format!("{marker}{marker}{content}{marker}{marker}")
             }
             "em" | "i" => {
-                ctx.in_inline = true;
-                let content = render_children(url, dom, id, ctx)?;
-                ctx.in_inline = old_inline_status;
-                format!("*{content}*")
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                let marker = ctx.options.emphasis_marker.as_str();
+                format!("{marker}{content}{marker}")
             }
-            "br" => "<br>".to_string(),
-            _ => {
-                ctx.in_inline = true;
-                let content = render_children(url, dom, id, ctx)?;
-                ctx.in_inline = old_inline_status;
-                content
+            "del" | "s" | "strike" => {
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                Self::wrap_delimited(&content, "~~")
+            }
+            "mark" => {
+                // Obsidian highlight syntax
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                Self::wrap_delimited(&content, "==")
+            }
+            "sub" | "sup" | "kbd" => {
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                Self::wrap_html(&content, tag_name, None, ctx.options.inline_html)
+            }
+            "abbr" => {
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                let title = attrs.get("title").map(|title| title.trim());
+                Self::wrap_html(
+                    &content,
+                    tag_name,
+                    title.filter(|t| !t.is_empty()),
+                    ctx.options.inline_html,
+                )
+            }
+            "q" => {
+                let content = self.render_inline_children(url, dom, id, ctx)?;
+                let (open, close) = if Self::is_nested_quote(dom, id) {
+                    ('\u{2018}', '\u{2019}')
+                } else {
+                    ('\u{201C}', '\u{201D}')
+                };
+                format!("{open}{}{close}", content.trim())
             }
+            "br" => "<br>".to_string(),
+            // <span>, <ins> and <small> have no Markdown equivalent, text is preserved as is
+            _ => self.render_inline_children(url, dom, id, ctx)?,
         };
         self.update_last_char(ctx, &result);
         Ok(result)
@@ -66,3 +208,111 @@ impl Renderer for Inline {
 }
 
 pub static INLINE: Inline = Inline;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::ConvertOptions;
+    use crate::parser;
+    use crate::renderers;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// strikethrough and highlight tests
+    #[rstest]
+    #[case("<del>removed</del>", "~~removed~~")]
+    #[case("<s>outdated</s>", "~~outdated~~")]
+    #[case("<strike>old</strike>", "~~old~~")]
+    #[case("<mark>important</mark>", "==important==")]
+    #[case("<p>Price: <del>$10</del> $8</p>", "Price: ~~\\$10~~ \\$8\n\n")]
+    #[case(
+        "<p>This is<mark> highlighted </mark>text</p>",
+        "This is ==highlighted== text\n\n"
+    )]
+    #[case("<del><strong>bold</strong> removed</del>", "~~**bold** removed~~")]
+    #[case("<del></del>", "")]
+    #[case("<mark>  </mark>", "")]
+    fn test_strikethrough_and_highlight(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render inline element");
+        assert_eq!(result, expected);
+    }
+
+    /// HTML passthrough tests (default)
+    #[rstest]
+    #[case("<p>H<sub>2</sub>O</p>", "H<sub>2</sub>O\n\n")]
+    #[case("<p>E = mc<sup>2</sup></p>", "E = mc<sup>2</sup>\n\n")]
+    #[case(
+        "<p>Press <kbd>Ctrl</kbd>+<kbd>C</kbd></p>",
+        "Press <kbd>Ctrl</kbd>+<kbd>C</kbd>\n\n"
+    )]
+    #[case(
+        r#"<abbr title="HyperText Markup Language">HTML</abbr>"#,
+        r#"<abbr title="HyperText Markup Language">HTML</abbr>"#
+    )]
+    #[case(
+        r#"<abbr title="Say &quot;hi&quot;">SH</abbr>"#,
+        r#"<abbr title="Say &quot;hi&quot;">SH</abbr>"#
+    )]
+    #[case("<abbr>CSS</abbr>", "<abbr>CSS</abbr>")]
+    #[case("<sup></sup>", "")]
+    fn test_html_passthrough(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render inline element");
+        assert_eq!(result, expected);
+    }
+
+    /// text-only mode for elements without Markdown equivalent
+    #[rstest]
+    #[case("<p>H<sub>2</sub>O</p>", "H2O\n\n")]
+    #[case("<p>x<sup>2</sup></p>", "x2\n\n")]
+    #[case("<kbd>Enter</kbd>", "Enter")]
+    #[case(r#"<abbr title="World Wide Web">WWW</abbr>"#, "WWW")]
+    fn test_text_only_mode(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().inline_html(InlineHtml::TextOnly),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render inline element");
+        assert_eq!(result, expected);
+    }
+
+    /// inline quotes
+    #[rstest]
+    #[case("<q>Hello</q>", "\u{201C}Hello\u{201D}")]
+    #[case(
+        "<p>He said <q>she said <q>hi</q></q>.</p>",
+        "He said \u{201C}she said \u{2018}hi\u{2019}\u{201D}.\n\n"
+    )]
+    fn test_inline_quotes(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render inline quote");
+        assert_eq!(result, expected);
+    }
+
+    /// existing emphasis and transparent elements
+    #[rstest]
+    #[case("<strong>bold</strong>", "**bold**")]
+    #[case("<b>bold</b>", "**bold**")]
+    #[case("<em>italic</em>", "*italic*")]
+    #[case("<i>italic</i>", "*italic*")]
+    #[case("<ins>inserted</ins>", "inserted")]
+    #[case("<small>fine print</small>", "fine print")]
+    #[case("<span>plain</span>", "plain")]
+    #[case("<p>line<br>break</p>", "line<br>break\n\n")]
+    fn test_basic_inline_elements(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render inline element");
+        assert_eq!(result, expected);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Context, Renderer, render_children};
+use super::{Context, Renderer, footnote::FOOTNOTE, render_children};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
@@ -27,9 +27,268 @@ impl ListType {
     }
 }
 
+/// Marker of unordered list items
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BulletMarker {
+    /// `- item`
+    #[default]
+    Dash,
+    /// `* item`
+    Asterisk,
+    /// `+ item`
+    Plus,
+}
+
+impl BulletMarker {
+    const fn as_char(self) -> char {
+        match self {
+            Self::Dash => '-',
+            Self::Asterisk => '*',
+            Self::Plus => '+',
+        }
+    }
+}
+
+/// How ordered list items are numbered
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ListNumbering {
+    /// Real numbers honouring `start`, `reversed`, `<li value>` and `type`
+    #[default]
+    Sequential,
+    /// Every item is `1.` and the Markdown renderer numbers them.
+    /// Lists counting down or jumping keep their real numbers as labels
+    Lazy,
+}
+
+/// Marker style from the `type` attribute of <ol>
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumberStyle {
+    Decimal,
+    LowerAlpha,
+    UpperAlpha,
+    LowerRoman,
+    UpperRoman,
+}
+
+impl NumberStyle {
+    fn from_attr(value: Option<&String>) -> Self {
+        match value.map(|v| v.trim()) {
+            Some("a") => Self::LowerAlpha,
+            Some("A") => Self::UpperAlpha,
+            Some("i") => Self::LowerRoman,
+            Some("I") => Self::UpperRoman,
+            _ => Self::Decimal,
+        }
+    }
+
+    /// Label for non-decimal styles, falls back to decimal where the style has no representation
+    fn label(self, number: i64) -> String {
+        let label = match self {
+            Self::Decimal => None,
+            Self::LowerAlpha | Self::UpperAlpha => to_alpha(number),
+            Self::LowerRoman | Self::UpperRoman => to_roman(number),
+        }
+        .unwrap_or_else(|| number.to_string());
+
+        match self {
+            Self::UpperAlpha | Self::UpperRoman => label.to_uppercase(),
+            _ => label,
+        }
+    }
+}
+
+/// 1 -> a, 26 -> z, 27 -> aa
+fn to_alpha(number: i64) -> Option<String> {
+    if number < 1 {
+        return None;
+    }
+    let mut n = number;
+    let mut label = Vec::new();
+    while n > 0 {
+        n -= 1;
+        label.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    Some(label.into_iter().rev().collect())
+}
+
+fn to_roman(number: i64) -> Option<String> {
+    const NUMERALS: [(i64, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    if !(1..4000).contains(&number) {
+        return None;
+    }
+    let mut n = number;
+    let mut label = String::new();
+    for (value, numeral) in NUMERALS {
+        while n >= value {
+            label.push_str(numeral);
+            n -= value;
+        }
+    }
+    Some(label)
+}
+
+/// Numbering state of the ordered list being rendered
+#[derive(Debug, Clone, Copy)]
+pub struct ListCounter {
+    next: i64,
+    step: i64,
+    style: NumberStyle,
+    /// Items are bullets labelled with their number: Markdown only takes the first number
+    /// of a list and counts up from it, which loses letters, numerals, reversed lists
+    /// and `<li value>` jumps
+    labelled: bool,
+    /// Width of the widest marker including the trailing space
+    indent: usize,
+}
+
+impl ListCounter {
+    /// Takes the number for an item, `<li value>` resets the sequence
+    fn take(&mut self, value: Option<i64>) -> i64 {
+        let number = value.unwrap_or(self.next);
+        self.next = number.saturating_add(self.step);
+        number
+    }
+
+    fn marker(&self, number: i64, bullet: BulletMarker) -> String {
+        if !self.labelled {
+            return format!("{number}.");
+        }
+        let label = self.style.label(number);
+        // "- 3. item" would start a nested ordered list
+        let dot = if label.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            "\\."
+        } else {
+            "."
+        };
+        format!("{} {label}{dot}", bullet.as_char())
+    }
+}
+
 pub struct List;
 
 impl List {
+    fn parse_number(value: Option<&String>) -> Option<i64> {
+        value.and_then(|v| v.trim().parse().ok())
+    }
+
+    fn item_value(dom: &Dom, id: NodeId) -> Option<i64> {
+        dom.get_element_data(id)
+            .ok()
+            .and_then(|(_, attrs)| Self::parse_number(attrs.get("value")))
+    }
+
+    /// Creates the numbering for an <ol> from its `start`, `reversed` and `type` attributes
+    fn create_counter(&self, dom: &Dom, id: NodeId) -> Result<ListCounter, ConvertError> {
+        let (_, attrs) = dom.get_element_data(id)?;
+        let items: Vec<NodeId> = dom
+            .iter_children(id)?
+            .copied()
+            .filter(|&child_id| {
+                dom.get_element_data(child_id)
+                    .is_ok_and(|(tag, _)| tag.local.as_ref() == "li")
+            })
+            .collect();
+
+        let reversed = attrs.contains_key("reversed");
+        let start = Self::parse_number(attrs.get("start")).unwrap_or(if reversed {
+            items.len() as i64
+        } else {
+            1
+        });
+        let style = NumberStyle::from_attr(attrs.get("type"));
+
+        let mut counter = ListCounter {
+            next: start,
+            step: if reversed { -1 } else { 1 },
+            style,
+            labelled: style != NumberStyle::Decimal,
+            indent: ListType::Ordered.indent_size(),
+        };
+
+        // Markdown numbers keep only counting up by one from a first number of at most 9 digits
+        let mut simulated = counter;
+        let numbers: Vec<i64> = items
+            .iter()
+            .map(|&item| simulated.take(Self::item_value(dom, item)))
+            .collect();
+        let is_markdown_sequence = numbers.first().is_none_or(|&first| {
+            (0..=999_999_999).contains(&first)
+                && numbers
+                    .iter()
+                    .zip(first..)
+                    .all(|(&number, expected)| number == expected)
+        });
+        counter.labelled |= !is_markdown_sequence;
+
+        // item content is aligned after the widest number ("10. " needs 4 spaces),
+        // labelled items are bullets and keep the default indentation
+        if !counter.labelled {
+            let mut simulated = counter;
+            let widest = items
+                .iter()
+                .map(|&item| {
+                    let number = simulated.take(Self::item_value(dom, item));
+                    simulated.marker(number, BulletMarker::Dash).len() + 1
+                })
+                .max()
+                .unwrap_or_default();
+            counter.indent = counter.indent.max(widest);
+        }
+
+        Ok(counter)
+    }
+
+    /// Finds the checkbox of a task list item, returning whether it is checked.
+    ///
+    /// The checkbox has to be the first content of the item, optionally wrapped
+    /// (`<p><input type="checkbox"> Task</p>` in loose GitHub task lists).
+    /// Supports `<input type="checkbox">`, ARIA checkboxes and Notion's `div.checkbox`.
+    fn task_checkbox(dom: &Dom, id: NodeId) -> Option<bool> {
+        for &child_id in dom.iter_children(id).ok()? {
+            match &dom.node(child_id)?.data {
+                NodeData::Text(text) if text.trim().is_empty() => continue,
+                NodeData::Comment(_) => continue,
+                NodeData::Element { tag, attrs } => {
+                    let has_class = |name: &str| {
+                        attrs
+                            .get("class")
+                            .is_some_and(|class| class.split_whitespace().any(|c| c == name))
+                    };
+
+                    return match tag.local.as_ref() {
+                        "input" => attrs
+                            .get("type")
+                            .is_some_and(|t| t.eq_ignore_ascii_case("checkbox"))
+                            .then(|| attrs.contains_key("checked")),
+                        _ if attrs.get("role").is_some_and(|role| role == "checkbox") => {
+                            Some(attrs.get("aria-checked").is_some_and(|c| c == "true"))
+                        }
+                        "div" | "span" if has_class("checkbox") => Some(has_class("checkbox-on")),
+                        "p" | "label" | "span" | "div" => Self::task_checkbox(dom, child_id),
+                        _ => None,
+                    };
+                }
+                _ => return None,
+            }
+        }
+        None
+    }
+
     fn is_ordered_list(&self, dom: &Dom, id: NodeId) -> bool {
         dom.get_parent(id)
             .ok()
@@ -153,7 +412,12 @@ impl Renderer for List {
                 NodeData::Element { tag, .. } => Some(tag.local.as_ref()),
                 _ => None,
             })
-            .map(|tag| matches!(tag, "ul" | "ol" | "li"))
+            .map(|tag| match tag {
+                // footnote definitions are rendered by the footnote renderer
+                "ul" | "ol" => !FOOTNOTE.matches(dom, id),
+                "li" => true,
+                _ => false,
+            })
             .unwrap_or(false)
     }
 
@@ -187,9 +451,26 @@ impl List {
         let list_type = ListType::from_tag(tag)
             .ok_or_else(|| ConvertError::Unsupported(format!("Unknown list tag: {tag}")))?;
 
-        ctx.list_depth += list_type.indent_size();
-        let content = render_children(url, dom, id, ctx)?;
-        ctx.list_depth -= list_type.indent_size();
+        let counter = if list_type == ListType::Ordered {
+            let counter = self.create_counter(dom, id)?;
+            // lazy numbering still labels the numbers Markdown can't express
+            match ctx.options.list_numbering {
+                ListNumbering::Sequential => Some(counter),
+                ListNumbering::Lazy => {
+                    (counter.labelled && counter.style == NumberStyle::Decimal).then_some(counter)
+                }
+            }
+        } else {
+            None
+        };
+        let indent = counter.map_or(list_type.indent_size(), |counter| counter.indent);
+        let old_counter = std::mem::replace(&mut ctx.list_counter, counter);
+
+        ctx.list_depth += indent;
+        let content = render_children(url, dom, id, ctx);
+        ctx.list_depth -= indent;
+        ctx.list_counter = old_counter;
+        let content = content?;
 
         if ctx.list_depth == 0 {
             if content.trim().is_empty() {
@@ -212,6 +493,28 @@ impl List {
         let needs_spacing = self.needs_spacing_before_item(dom, id);
         let prefix = if needs_spacing { "\n" } else { "" };
 
+        // the number is taken even for empty items to keep the sequence of the original page
+        let ordered_marker = if self.is_ordered_list(dom, id) {
+            Some(match ctx.list_counter.as_mut() {
+                Some(counter) => {
+                    let number = counter.take(Self::item_value(dom, id));
+                    (
+                        counter.marker(number, ctx.options.bullet_marker),
+                        counter.indent,
+                    )
+                }
+                None => ("1.".to_string(), ListType::Ordered.indent_size()),
+            })
+        } else {
+            None
+        };
+
+        let checkbox = match Self::task_checkbox(dom, id) {
+            Some(true) => "[x] ",
+            Some(false) => "[ ] ",
+            None => "",
+        };
+
         ctx.list_first_item = true;
         let content = self.render_list_item_children(url, dom, id, ctx)?;
 
@@ -220,13 +523,27 @@ impl List {
         }
 
         // list content should not have leading/trailing whitespace - saturating_sub(n)
-        let marker = if self.is_ordered_list(dom, id) {
-            format!("{}1.", " ".repeat(ctx.list_depth.saturating_sub(3)))
+        let marker = if let Some((marker, indent)) = ordered_marker {
+            format!(
+                "{}{marker}",
+                " ".repeat(ctx.list_depth.saturating_sub(indent))
+            )
         } else {
-            format!("{}-", " ".repeat(ctx.list_depth.saturating_sub(2)))
+            format!(
+                "{}{}",
+                " ".repeat(ctx.list_depth.saturating_sub(2)),
+                ctx.options.bullet_marker.as_char()
+            )
         };
 
-        Ok(format!("{prefix}{marker} {content}\n"))
+        if checkbox.is_empty() {
+            Ok(format!("{prefix}{marker} {content}\n"))
+        } else {
+            Ok(format!(
+                "{prefix}{marker} {checkbox}{}\n",
+                content.trim_start()
+            ))
+        }
     }
 }
 
@@ -235,6 +552,7 @@ pub static LIST: List = List;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::ConvertOptions;
     use crate::parser;
     use crate::renderers;
     use indoc::indoc;
@@ -267,7 +585,7 @@ mod tests {
         "<ol><li>First item</li><li>Second item</li></ol>",
         indoc! {r#"
             1. First item
-            1. Second item
+            2. Second item
 
             "#}
     )]
@@ -296,7 +614,7 @@ mod tests {
         indoc! {r#"
             1. First
                - Nested unordered
-            1. Second
+            2. Second
 
             "#}
     )]
@@ -382,7 +700,7 @@ mod tests {
                command one
                ```
 
-            1. Step two
+            2. Step two
 
                ```
                command two
@@ -450,7 +768,7 @@ mod tests {
 
                More details here
 
-            1. Another item
+            2. Another item
 
             "#}
     )]
@@ -467,7 +785,7 @@ mod tests {
     #[case("<ul><li></li></ul>", "")]
     #[case("<ul><li>   </li></ul>", "")]
     #[case("<ul><li><p></p></li></ul>", "")]
-    #[case("<ol><li></li><li>Second</li></ol>", "1. Second\n\n")]
+    #[case("<ol><li></li><li>Second</li></ol>", "2. Second\n\n")]
     fn test_empty_and_edge_cases(#[case] html: &str, #[case] expected: &str) {
         let dom = parser::parse_html(html).expect("Failed to parse HTML");
         let mut context = Context::default();
@@ -476,6 +794,167 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    /// Ordered list numbering attributes
+    #[rstest]
+    #[case(
+        r#"<ol start="5"><li>Five</li><li>Six</li></ol>"#,
+        "5. Five\n6. Six\n\n"
+    )]
+    #[case(
+        "<ol reversed><li>Three</li><li>Two</li><li>One</li></ol>",
+        "- 3\\. Three\n- 2\\. Two\n- 1\\. One\n\n"
+    )]
+    #[case(
+        r#"<ol reversed start="10"><li>Ten</li><li>Nine</li></ol>"#,
+        "- 10\\. Ten\n- 9\\. Nine\n\n"
+    )]
+    #[case(
+        r#"<ol><li>One</li><li value="7">Seven</li><li>Eight</li></ol>"#,
+        "- 1\\. One\n- 7\\. Seven\n- 8\\. Eight\n\n"
+    )]
+    // a value on the first item or one continuing the sequence is kept by Markdown
+    #[case(
+        r#"<ol><li value="4">Four</li><li value="5">Five</li><li>Six</li></ol>"#,
+        "4. Four\n5. Five\n6. Six\n\n"
+    )]
+    #[case(r#"<ol reversed><li>Only</li></ol>"#, "1. Only\n\n")]
+    #[case(
+        r#"<ol start="-2"><li>Minus two</li><li>Minus one</li></ol>"#,
+        "- -2\\. Minus two\n- -1\\. Minus one\n\n"
+    )]
+    #[case(
+        r#"<ol start="abc"><li>Invalid start</li></ol>"#,
+        "1. Invalid start\n\n"
+    )]
+    #[case(
+        r#"<ol type="a"><li>First</li><li>Second</li></ol>"#,
+        "- a. First\n- b. Second\n\n"
+    )]
+    #[case(
+        r#"<ol type="I" start="3"><li>Third</li><li>Fourth</li></ol>"#,
+        "- III. Third\n- IV. Fourth\n\n"
+    )]
+    #[case(
+        r#"<ol type="i"><li>Item<ul><li>Nested</li></ul></li></ol>"#,
+        "- i. Item\n   - Nested\n\n"
+    )]
+    #[case(
+        r#"<ol start="9"><li>Nine</li><li>Ten<ul><li>Nested</li></ul></li></ol>"#,
+        indoc! {r#"
+            9. Nine
+            10. Ten
+                - Nested
+
+            "#}
+    )]
+    #[case(
+        r#"<ol start="3"><li>Outer<ol start="7"><li>Inner</li></ol></li><li>Next</li></ol>"#,
+        indoc! {r#"
+            3. Outer
+               7. Inner
+            4. Next
+
+            "#}
+    )]
+    fn test_ordered_list_numbering(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render ordered list");
+        assert_eq!(result, expected);
+    }
+
+    /// lazy numbering keeps every marker as "1.", except for numbers Markdown can't count
+    #[rstest]
+    #[case(r#"<ol start="5"><li>A</li><li>B</li></ol>"#, "1. A\n1. B\n\n")]
+    #[case(
+        r#"<ol start="5" reversed><li>A</li><li value="9">B</li></ol>"#,
+        "- 5\\. A\n- 9\\. B\n\n"
+    )]
+    #[case(r#"<ol type="a"><li>A</li><li>B</li></ol>"#, "1. A\n1. B\n\n")]
+    fn test_lazy_list_numbering(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().list_numbering(ListNumbering::Lazy),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render ordered list");
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(1, Some("a"))]
+    #[case(26, Some("z"))]
+    #[case(27, Some("aa"))]
+    #[case(0, None)]
+    fn test_to_alpha(#[case] number: i64, #[case] expected: Option<&str>) {
+        assert_eq!(to_alpha(number).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(4, Some("iv"))]
+    #[case(1994, Some("mcmxciv"))]
+    #[case(0, None)]
+    #[case(4000, None)]
+    fn test_to_roman(#[case] number: i64, #[case] expected: Option<&str>) {
+        assert_eq!(to_roman(number).as_deref(), expected);
+    }
+
+    /// Task list checkboxes
+    #[rstest]
+    #[case(
+        r#"<ul><li><input type="checkbox" checked> Done</li><li><input type="checkbox"> Todo</li></ul>"#,
+        "- [x] Done\n- [ ] Todo\n\n"
+    )]
+    #[case(
+        r#"<ul class="contains-task-list">
+            <li class="task-list-item"><input type="checkbox" class="task-list-item-checkbox" disabled checked> Write tests</li>
+            <li class="task-list-item"><input type="checkbox" class="task-list-item-checkbox" disabled> Ship it
+                <ul class="contains-task-list">
+                    <li class="task-list-item"><input type="checkbox" class="task-list-item-checkbox" disabled checked> Nested task</li>
+                </ul>
+            </li>
+        </ul>"#,
+        indoc! {r#"
+            - [x] Write tests
+            - [ ] Ship it
+              - [x] Nested task
+
+            "#}
+    )]
+    #[case(
+        r#"<ul><li class="task-list-item"><p><input type="checkbox" checked> Loose task</p></li></ul>"#,
+        "- [x] Loose task\n\n"
+    )]
+    #[case(
+        r#"<ol><li><input type="checkbox" checked>First step</li><li><input type="checkbox">Second step</li></ol>"#,
+        "1. [x] First step\n2. [ ] Second step\n\n"
+    )]
+    #[case(
+        r#"<ul class="to-do-list"><li><div class="checkbox checkbox-on"></div> <span class="to-do-children-checked">Notion done</span></li><li><div class="checkbox checkbox-off"></div> <span>Notion todo</span></li></ul>"#,
+        "- [x] Notion done\n- [ ] Notion todo\n\n"
+    )]
+    #[case(
+        r#"<ul><li><span role="checkbox" aria-checked="true"></span>ARIA task</li></ul>"#,
+        "- [x] ARIA task\n\n"
+    )]
+    #[case(
+        r#"<ul><li><label><input type="checkbox"> Labelled</label></li></ul>"#,
+        "- [ ] Labelled\n\n"
+    )]
+    #[case(
+        r#"<ul><li>Text before <input type="checkbox" checked></li></ul>"#,
+        "- Text before\n\n"
+    )]
+    fn test_task_lists(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render task list");
+        assert_eq!(result, expected);
+    }
+
     /// Mixed list types
     #[rstest]
     #[case(
@@ -534,7 +1013,7 @@ mod tests {
                my-tool --version
                ```
 
-            1. Configure the tool with your settings.
+            2. Configure the tool with your settings.
 
             "#}
     )]
@@ -578,7 +1057,7 @@ mod tests {
                   command
                   ```
 
-               1. Sub-step B
+               2. Sub-step B
 
             "#}
     )]
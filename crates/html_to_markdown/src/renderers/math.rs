@@ -0,0 +1,712 @@
+use super::{Context, INLINE_TAGS, Renderer};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Classes of rendered math whose TeX source is found inside the element
+const MATH_CLASSES: [&str; 4] = ["katex", "katex-display", "zenn-katex", "MathJax"];
+
+/// Classes of MathJax v2 output. The TeX source is in the following `<script type="math/tex">`
+const MATHJAX_OUTPUT_CLASSES: [&str; 5] = [
+    "MathJax",
+    "MathJax_Display",
+    "MathJax_SVG",
+    "MathJax_SVG_Display",
+    "MathJax_CHTML",
+];
+
+/// Classes marking display (block) math
+const DISPLAY_CLASSES: [&str; 4] = [
+    "katex-display",
+    "MathJax_Display",
+    "display",
+    "math-display",
+];
+
+/// Operators in <mi>/<mo> that are written as TeX commands
+const FUNCTION_NAMES: [&str; 16] = [
+    "sin", "cos", "tan", "cot", "sec", "csc", "log", "ln", "exp", "lim", "max", "min", "sup",
+    "inf", "det", "gcd",
+];
+
+static TEX_SYMBOLS: LazyLock<HashMap<char, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        ('α', r"\alpha"),
+        ('β', r"\beta"),
+        ('γ', r"\gamma"),
+        ('δ', r"\delta"),
+        ('ε', r"\epsilon"),
+        ('ζ', r"\zeta"),
+        ('η', r"\eta"),
+        ('θ', r"\theta"),
+        ('κ', r"\kappa"),
+        ('λ', r"\lambda"),
+        ('μ', r"\mu"),
+        ('ν', r"\nu"),
+        ('ξ', r"\xi"),
+        ('π', r"\pi"),
+        ('ρ', r"\rho"),
+        ('σ', r"\sigma"),
+        ('τ', r"\tau"),
+        ('φ', r"\phi"),
+        ('χ', r"\chi"),
+        ('ψ', r"\psi"),
+        ('ω', r"\omega"),
+        ('Γ', r"\Gamma"),
+        ('Δ', r"\Delta"),
+        ('Θ', r"\Theta"),
+        ('Λ', r"\Lambda"),
+        ('Ξ', r"\Xi"),
+        ('Π', r"\Pi"),
+        ('Σ', r"\Sigma"),
+        ('Φ', r"\Phi"),
+        ('Ψ', r"\Psi"),
+        ('Ω', r"\Omega"),
+        ('×', r"\times"),
+        ('÷', r"\div"),
+        ('·', r"\cdot"),
+        ('⋅', r"\cdot"),
+        ('−', "-"),
+        ('±', r"\pm"),
+        ('≤', r"\leq"),
+        ('≥', r"\geq"),
+        ('≠', r"\neq"),
+        ('≈', r"\approx"),
+        ('≡', r"\equiv"),
+        ('∞', r"\infty"),
+        ('→', r"\to"),
+        ('←', r"\leftarrow"),
+        ('⇒', r"\Rightarrow"),
+        ('⇔', r"\Leftrightarrow"),
+        ('∑', r"\sum"),
+        ('∏', r"\prod"),
+        ('∫', r"\int"),
+        ('∂', r"\partial"),
+        ('∇', r"\nabla"),
+        ('∈', r"\in"),
+        ('∉', r"\notin"),
+        ('⊂', r"\subset"),
+        ('⊆', r"\subseteq"),
+        ('∪', r"\cup"),
+        ('∩', r"\cap"),
+        ('∀', r"\forall"),
+        ('∃', r"\exists"),
+        ('¬', r"\neg"),
+        ('∧', r"\land"),
+        ('∨', r"\lor"),
+        ('…', r"\ldots"),
+        ('⋯', r"\cdots"),
+        ('{', r"\{"),
+        ('}', r"\}"),
+    ])
+});
+
+/// Accents in <mover> that have a TeX command
+fn accent_command(accent: &str) -> Option<&'static str> {
+    match accent {
+        "^" | "ˆ" => Some(r"\hat"),
+        "¯" | "‾" | "_" => Some(r"\overline"),
+        "→" | "⃗" => Some(r"\vec"),
+        "~" | "˜" => Some(r"\tilde"),
+        "˙" | "." => Some(r"\dot"),
+        "¨" => Some(r"\ddot"),
+        _ => None,
+    }
+}
+
+/// Represents a math renderer for KaTeX, MathJax, MathML and pandoc style math.
+///
+/// The TeX source is taken from `<annotation encoding="application/x-tex">` or
+/// `<script type="math/tex">` when available, MathML without annotation is converted.
+pub struct Math;
+
+impl Math {
+    fn has_class(dom: &Dom, id: NodeId, classes: &[&str]) -> bool {
+        dom.get_element_data(id).is_ok_and(|(_, attrs)| {
+            attrs
+                .get("class")
+                .is_some_and(|class| class.split_whitespace().any(|name| classes.contains(&name)))
+        })
+    }
+
+    /// pandoc math (`<span class="math inline">`, `<span class="math display">`) or another
+    /// `.math` element holding TeX source; a bare `.math` container is ordinary content
+    fn is_pandoc_math(dom: &Dom, id: NodeId) -> bool {
+        if !Self::has_class(dom, id, &["math"]) {
+            return false;
+        }
+        let is_pandoc_span = dom
+            .get_element_data(id)
+            .is_ok_and(|(tag, _)| tag.local.as_ref() == "span")
+            && Self::has_class(dom, id, &["inline", "display"]);
+        is_pandoc_span
+            || Self::find_descendant(dom, id, &|node| {
+                Self::is_tex_annotation(dom, node) || Self::is_tex_script(dom, node)
+            })
+            .is_some()
+    }
+
+    /// `<script type="math/tex">` (MathJax v2 source)
+    pub fn is_tex_script(dom: &Dom, id: NodeId) -> bool {
+        dom.get_element_data(id).is_ok_and(|(tag, attrs)| {
+            tag.local.as_ref() == "script"
+                && attrs
+                    .get("type")
+                    .is_some_and(|t| t.trim_start().starts_with("math/tex"))
+        })
+    }
+
+    /// MathJax v2 output followed by its TeX source script is skipped,
+    /// the script renders the math instead
+    fn is_followed_by_tex_script(dom: &Dom, id: NodeId) -> bool {
+        let Ok(Some(parent_id)) = dom.get_parent(id) else {
+            return false;
+        };
+        let Ok(siblings) = dom.iter_children(parent_id) else {
+            return false;
+        };
+
+        siblings
+            .skip_while(|&&sibling| sibling != id)
+            .skip(1)
+            .find(|&&sibling| {
+                matches!(
+                    dom.node(sibling).map(|n| &n.data),
+                    Some(NodeData::Element { .. })
+                )
+            })
+            .is_some_and(|&sibling| Self::is_tex_script(dom, sibling))
+    }
+
+    fn find_descendant(
+        dom: &Dom,
+        id: NodeId,
+        predicate: &dyn Fn(NodeId) -> bool,
+    ) -> Option<NodeId> {
+        if predicate(id) {
+            return Some(id);
+        }
+        dom.iter_children(id)
+            .ok()?
+            .find_map(|&child_id| Self::find_descendant(dom, child_id, predicate))
+    }
+
+    fn is_tex_annotation(dom: &Dom, id: NodeId) -> bool {
+        dom.get_element_data(id).is_ok_and(|(tag, attrs)| {
+            tag.local.as_ref() == "annotation"
+                && attrs
+                    .get("encoding")
+                    .is_some_and(|encoding| encoding.to_ascii_lowercase().contains("tex"))
+        })
+    }
+
+    fn is_math_element(dom: &Dom, id: NodeId) -> bool {
+        dom.get_element_data(id)
+            .is_ok_and(|(tag, _)| tag.local.as_ref() == "math")
+    }
+
+    fn is_display(dom: &Dom, id: NodeId) -> bool {
+        if Self::has_class(dom, id, &DISPLAY_CLASSES) {
+            return true;
+        }
+        let Ok((tag, attrs)) = dom.get_element_data(id) else {
+            return false;
+        };
+
+        match tag.local.as_ref() {
+            "script" => attrs
+                .get("type")
+                .is_some_and(|t| t.contains("mode=display")),
+            "eqn" => true,
+            "mjx-container" => attrs.get("display").is_some_and(|d| d == "true"),
+            _ => Self::find_descendant(dom, id, &|node| {
+                dom.get_element_data(node).is_ok_and(|(tag, attrs)| {
+                    tag.local.as_ref() == "math"
+                        && attrs.get("display").is_some_and(|d| d == "block")
+                })
+            })
+            .is_some(),
+        }
+    }
+
+    /// Removes `\(…\)` / `\[…\]` delimiters left in the text by pandoc and MathJax sources
+    fn strip_delimiters(tex: &str) -> &str {
+        let tex = tex.trim();
+        [("\\(", "\\)"), ("\\[", "\\]"), ("$$", "$$"), ("$", "$")]
+            .iter()
+            .find_map(|(open, close)| {
+                tex.strip_prefix(open)
+                    .and_then(|rest| rest.strip_suffix(close))
+                    .filter(|inner| !inner.is_empty())
+            })
+            .map_or(tex, str::trim)
+    }
+
+    /// Finds the TeX source of a math element
+    fn extract_tex(dom: &Dom, id: NodeId) -> String {
+        if Self::is_tex_script(dom, id) {
+            return dom.collect_text_content(id);
+        }
+        if let Some(annotation) =
+            Self::find_descendant(dom, id, &|node| Self::is_tex_annotation(dom, node))
+        {
+            return dom.collect_text_content(annotation);
+        }
+        if let Some(script) = Self::find_descendant(dom, id, &|node| Self::is_tex_script(dom, node))
+        {
+            return dom.collect_text_content(script);
+        }
+        if let Some(math) = Self::find_descendant(dom, id, &|node| Self::is_math_element(dom, node))
+        {
+            return mathml_to_tex(dom, math);
+        }
+        // pandoc (<span class="math inline">\(x\)</span>) and Zenn (<eq class="zenn-katex">)
+        // keep the TeX source as text
+        Self::strip_delimiters(&dom.collect_text_content(id)).to_string()
+    }
+
+    /// Whether display math directly follows inline content on the same line
+    fn follows_inline_content(dom: &Dom, id: NodeId) -> bool {
+        let mut current = id;
+        loop {
+            let Ok(Some(parent_id)) = dom.get_parent(current) else {
+                return false;
+            };
+            let Ok(siblings) = dom.iter_children(parent_id) else {
+                return false;
+            };
+
+            let previous = siblings
+                .take_while(|&&sibling| sibling != current)
+                .filter(|&&sibling| match dom.node(sibling).map(|node| &node.data) {
+                    Some(NodeData::Text(text)) => !text.trim().is_empty(),
+                    Some(NodeData::Element { .. }) => true,
+                    _ => false,
+                })
+                .last();
+
+            match previous
+                .and_then(|&sibling| dom.node(sibling))
+                .map(|n| &n.data)
+            {
+                Some(NodeData::Text(_)) => return true,
+                Some(NodeData::Element { tag, .. }) => {
+                    return INLINE_TAGS.contains(&tag.local.as_ref());
+                }
+                _ => {}
+            }
+
+            match dom.get_element_data(parent_id) {
+                Ok((tag, _)) if INLINE_TAGS.contains(&tag.local.as_ref()) => current = parent_id,
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Formats TeX as inline `$…$` or display `$$…$$` math
+pub fn format_math(tex: &str, display: bool, ctx: &Context) -> String {
+    let tex = tex.trim();
+    if tex.is_empty() {
+        return String::new();
+    }
+
+    if !display {
+        // inline math has to stay on one line
+        let single_line = tex.split_whitespace().collect::<Vec<_>>().join(" ");
+        return format!("${single_line}$");
+    }
+
+    let indent = " ".repeat(ctx.list_depth);
+    let body = tex
+        .lines()
+        .map(|line| format!("{indent}{}", line.trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if ctx.list_depth > 0 {
+        format!("\n\n{indent}$$\n{body}\n{indent}$$")
+    } else {
+        format!("$$\n{body}\n$$\n\n")
+    }
+}
+
+impl Renderer for Math {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+        let NodeData::Element { tag, .. } = &node.data else {
+            return false;
+        };
+
+        matches!(tag.local.as_ref(), "math" | "mjx-container")
+            || Self::is_tex_script(dom, id)
+            || Self::has_class(dom, id, &MATH_CLASSES)
+            || Self::is_pandoc_math(dom, id)
+            || Self::has_class(dom, id, &MATHJAX_OUTPUT_CLASSES)
+            || Self::has_class(dom, id, &["MathJax_Preview"])
+    }
+
+    fn render(
+        &self,
+        _url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        if Self::has_class(dom, id, &["MathJax_Preview"])
+            || (Self::has_class(dom, id, &MATHJAX_OUTPUT_CLASSES)
+                && Self::is_followed_by_tex_script(dom, id))
+        {
+            return Ok(String::new());
+        }
+
+        let tex = Self::extract_tex(dom, id);
+        let display = Self::is_display(dom, id);
+        let math = format_math(&tex, display, ctx);
+
+        if display && ctx.list_depth == 0 && Self::follows_inline_content(dom, id) {
+            return Ok(format!("\n\n{math}"));
+        }
+        Ok(math)
+    }
+}
+
+pub static MATH: Math = Math;
+
+/// Appends a TeX fragment, separating a command from a following letter (`\alpha x`)
+fn push_tex(tex: &mut String, fragment: &str) {
+    let ends_with_command = {
+        let letters = tex
+            .chars()
+            .rev()
+            .take_while(char::is_ascii_alphabetic)
+            .count();
+        letters > 0 && tex[..tex.len() - letters].ends_with('\\')
+    };
+    if ends_with_command && fragment.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        tex.push(' ');
+    }
+    tex.push_str(fragment);
+}
+
+fn convert_token(text: &str) -> String {
+    let text = text.trim();
+    let mut tex = String::new();
+    for c in text.chars() {
+        match TEX_SYMBOLS.get(&c) {
+            Some(symbol) => push_tex(&mut tex, symbol),
+            None => push_tex(&mut tex, c.encode_utf8(&mut [0; 4])),
+        }
+    }
+    tex
+}
+
+/// Wraps a converted argument in braces unless it is a single character or command
+fn group(tex: &str) -> String {
+    let is_command = tex
+        .strip_prefix('\\')
+        .is_some_and(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()));
+    if tex.chars().count() == 1 || is_command {
+        tex.to_string()
+    } else {
+        format!("{{{tex}}}")
+    }
+}
+
+fn element_children(dom: &Dom, id: NodeId) -> Vec<NodeId> {
+    dom.iter_children(id)
+        .map(|children| {
+            children
+                .copied()
+                .filter(|&child| {
+                    matches!(
+                        dom.node(child).map(|n| &n.data),
+                        Some(NodeData::Element { .. })
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn convert_children(dom: &Dom, id: NodeId) -> String {
+    let mut tex = String::new();
+    for child in element_children(dom, id) {
+        push_tex(&mut tex, &mathml_to_tex(dom, child));
+    }
+    tex
+}
+
+/// Converts presentation MathML into TeX.
+///
+/// Covers the elements produced by common renderers; unknown elements are converted
+/// through their children.
+pub fn mathml_to_tex(dom: &Dom, id: NodeId) -> String {
+    let Ok((tag, attrs)) = dom.get_element_data(id) else {
+        return String::new();
+    };
+    let children = element_children(dom, id);
+    let arg = |index: usize| {
+        children
+            .get(index)
+            .map(|&child| mathml_to_tex(dom, child))
+            .unwrap_or_default()
+    };
+
+    match tag.local.as_ref() {
+        "mi" => {
+            let text = dom.collect_text_content(id);
+            let text = text.trim();
+            if FUNCTION_NAMES.contains(&text) {
+                format!("\\{text}")
+            } else if text.chars().count() > 1 {
+                format!("\\mathrm{{{text}}}")
+            } else {
+                convert_token(text)
+            }
+        }
+        "mn" => dom.collect_text_content(id).trim().to_string(),
+        "mo" => {
+            let text = dom.collect_text_content(id);
+            if FUNCTION_NAMES.contains(&text.trim()) {
+                format!("\\{}", text.trim())
+            } else {
+                convert_token(&text)
+            }
+        }
+        "mtext" => {
+            let text = dom.collect_text_content(id);
+            if text.trim().is_empty() {
+                " ".to_string()
+            } else {
+                format!("\\text{{{text}}}")
+            }
+        }
+        "mspace" => " ".to_string(),
+        "msup" => format!("{}^{}", group(&arg(0)), group(&arg(1))),
+        "msub" => format!("{}_{}", group(&arg(0)), group(&arg(1))),
+        "msubsup" | "munderover" => {
+            format!("{}_{}^{}", group(&arg(0)), group(&arg(1)), group(&arg(2)))
+        }
+        "mfrac" => format!("\\frac{{{}}}{{{}}}", arg(0), arg(1)),
+        "msqrt" => format!("\\sqrt{{{}}}", convert_children(dom, id)),
+        "mroot" => format!("\\sqrt[{}]{{{}}}", arg(1), arg(0)),
+        "mover" => {
+            let over = children
+                .get(1)
+                .map(|&child| dom.collect_text_content(child))
+                .unwrap_or_default();
+            match accent_command(over.trim()) {
+                Some(command) => format!("{command}{{{}}}", arg(0)),
+                None => format!("\\overset{{{}}}{{{}}}", arg(1), arg(0)),
+            }
+        }
+        "munder" => {
+            let base = arg(0);
+            if base.starts_with("\\lim") || base.starts_with("\\sum") || base.starts_with("\\prod")
+            {
+                format!("{base}_{}", group(&arg(1)))
+            } else {
+                format!("\\underset{{{}}}{{{base}}}", arg(1))
+            }
+        }
+        "mfenced" => {
+            let open = attrs.get("open").map_or("(", String::as_str);
+            let close = attrs.get("close").map_or(")", String::as_str);
+            let separator = attrs
+                .get("separators")
+                .map_or(",", |s| s.trim())
+                .chars()
+                .next()
+                .map(String::from)
+                .unwrap_or_default();
+            let items = children
+                .iter()
+                .map(|&child| mathml_to_tex(dom, child))
+                .collect::<Vec<_>>()
+                .join(&separator);
+            format!(
+                "\\left{}{items}\\right{}",
+                convert_token(open),
+                convert_token(close)
+            )
+        }
+        "mtable" => {
+            let rows = children
+                .iter()
+                .map(|&row| {
+                    element_children(dom, row)
+                        .iter()
+                        .map(|&cell| convert_children(dom, cell))
+                        .collect::<Vec<_>>()
+                        .join(" & ")
+                })
+                .collect::<Vec<_>>()
+                .join(r" \\ ");
+            format!("\\begin{{matrix}}{rows}\\end{{matrix}}")
+        }
+        // the first child of <semantics> is the presentation markup
+        "semantics" => arg(0),
+        "annotation" | "annotation-xml" => String::new(),
+        _ => convert_children(dom, id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// TeX source from KaTeX, MathJax v2 and pandoc/Zenn markup
+    #[rstest]
+    #[case(
+        r#"<p>Euler: <span class="katex"><span class="katex-mathml"><math><semantics><mrow><msup><mi>e</mi><mrow><mi>i</mi><mi>π</mi></mrow></msup></mrow><annotation encoding="application/x-tex">e^{i\pi}</annotation></semantics></math></span><span class="katex-html" aria-hidden="true"><span class="base">eiπ</span></span></span> = -1</p>"#,
+        "Euler: $e^{i\\pi}$ = -1\n\n"
+    )]
+    #[case(
+        r#"<span class="katex-display"><span class="katex"><span class="katex-mathml"><math display="block"><semantics><mrow><mi>x</mi></mrow><annotation encoding="application/x-tex">\sum_{i=1}^n i</annotation></semantics></math></span><span class="katex-html">soup</span></span></span>"#,
+        "$$\n\\sum_{i=1}^n i\n$$\n\n"
+    )]
+    #[case(
+        r#"<p>Inline <span class="MathJax_Preview">x</span><span class="MathJax" id="MathJax-Element-1-Frame"><span>x</span></span><script type="math/tex" id="MathJax-Element-1">x^2</script> text</p>"#,
+        "Inline $x^2$ text\n\n"
+    )]
+    #[case(
+        r#"<div class="MathJax_Display"><span class="MathJax">soup</span></div><script type="math/tex; mode=display">\int_0^1 f(x)\,dx</script>"#,
+        "$$\n\\int_0^1 f(x)\\,dx\n$$\n\n"
+    )]
+    #[case(r#"<p><span class="math inline">\(a + b\)</span></p>"#, "$a + b$\n\n")]
+    #[case(
+        r#"<span class="math display">\[E = mc^2\]</span>"#,
+        "$$\nE = mc^2\n$$\n\n"
+    )]
+    #[case(
+        r#"<p>Zenn <embed-katex><eq class="zenn-katex">a_n</eq></embed-katex></p>"#,
+        "Zenn $a_n$\n\n"
+    )]
+    #[case(
+        r#"<embed-katex display-mode="1"><eqn class="zenn-katex">f(x) = x^2</eqn></embed-katex>"#,
+        "$$\nf(x) = x^2\n$$\n\n"
+    )]
+    #[case(
+        r#"<mjx-container class="MathJax" jax="CHTML" display="true"><mjx-math>soup</mjx-math><mjx-assistive-mml><math display="block"><mfrac><mn>1</mn><mn>2</mn></mfrac></math></mjx-assistive-mml></mjx-container>"#,
+        "$$\n\\frac{1}{2}\n$$\n\n"
+    )]
+    #[case(r#"<span class="katex"></span>"#, "")]
+    #[case(
+        r#"<div class="code-frame" data-lang="math"><pre><code>\frac{1}{2}</code></pre></div>"#,
+        "$$\n\\frac{1}{2}\n$$\n\n"
+    )]
+    #[case(
+        r#"<pre><code class="language-math">a^2 + b^2</code></pre>"#,
+        "$$\na^2 + b^2\n$$\n\n"
+    )]
+    fn test_math_sources(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render math");
+        assert_eq!(result, expected);
+    }
+
+    /// display math placement in paragraphs and lists
+    #[rstest]
+    #[case(
+        r#"<p>Text before<span class="katex-display"><annotation encoding="application/x-tex">x</annotation></span></p>"#,
+        "Text before\n\n$$\nx\n$$\n\n"
+    )]
+    #[case(
+        r#"<ul><li>Item<span class="math display">a \\
+b</span></li></ul>"#,
+        indoc! {r#"
+            - Item
+
+              $$
+              a \\
+              b
+              $$
+
+            "#}
+    )]
+    fn test_display_math_placement(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render math");
+        assert_eq!(result, expected);
+    }
+
+    /// MathML without TeX annotation
+    #[rstest]
+    #[case("<math><mi>x</mi><mo>+</mo><mn>1</mn></math>", "$x+1$")]
+    #[case("<math><msup><mi>x</mi><mn>2</mn></msup></math>", "$x^2$")]
+    #[case(
+        "<math><msub><mi>a</mi><mrow><mi>i</mi><mo>+</mo><mn>1</mn></mrow></msub></math>",
+        "$a_{i+1}$"
+    )]
+    #[case("<math><mfrac><mi>a</mi><mi>b</mi></mfrac></math>", r"$\frac{a}{b}$")]
+    #[case("<math><msqrt><mi>x</mi></msqrt></math>", r"$\sqrt{x}$")]
+    #[case("<math><mroot><mi>x</mi><mn>3</mn></mroot></math>", r"$\sqrt[3]{x}$")]
+    #[case("<math><mi>α</mi><mi>x</mi></math>", r"$\alpha x$")]
+    #[case("<math><mi>sin</mi><mi>θ</mi></math>", r"$\sin\theta$")]
+    #[case("<math><mover><mi>v</mi><mo>→</mo></mover></math>", r"$\vec{v}$")]
+    #[case(
+        "<math><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover></math>",
+        r"$\sum_{i=1}^n$"
+    )]
+    #[case(
+        "<math><mtable><mtr><mtd><mn>1</mn></mtd><mtd><mn>0</mn></mtd></mtr><mtr><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd></mtr></mtable></math>",
+        r"$\begin{matrix}1 & 0 \\ 0 & 1\end{matrix}$"
+    )]
+    #[case("<math><mtext>if</mtext></math>", r"$\text{if}$")]
+    fn test_mathml_conversion(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render MathML");
+        assert_eq!(result, expected);
+    }
+
+    /// non-math scripts are still ignored
+    #[rstest]
+    #[case("<script>var x = 1;</script><p>Text</p>", "Text\n\n")]
+    #[case(r#"<script type="math/tex">y</script>"#, "$y$")]
+    fn test_script_handling(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render script");
+        assert_eq!(result, expected);
+    }
+
+    /// containers named `math` without TeX source keep their content
+    #[rstest]
+    #[case(
+        r#"<div class="math"><h2>Chapter 1</h2><p>Some long explanation of algebra.</p></div>"#,
+        "## Chapter 1\n\nSome long explanation of algebra.\n\n"
+    )]
+    #[case(
+        r#"<p>See <span class="math">the appendix</span></p>"#,
+        "See the appendix\n\n"
+    )]
+    #[case(
+        r#"<div class="math"><script type="math/tex">x^2</script></div>"#,
+        "$x^2$"
+    )]
+    fn test_math_class_without_tex(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render math");
+        assert_eq!(result, expected);
+    }
+}
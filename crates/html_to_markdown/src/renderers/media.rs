@@ -1,149 +1,486 @@
-use super::{Context, Renderer, render_children};
+use super::{
+    Context, Renderer, diagram::DIAGRAM, footnote::FOOTNOTE, heading::Heading, render_children,
+};
 use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
-    utils::format_list_content,
+    utils::{
+        cow_to_string,
+        escape::{EscapeContext, escape_markdown},
+        format_list_content,
+        url::{percent_decode, resolve_url},
+    },
 };
 use std::collections::HashMap;
 
+/// How images are emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ImageStyle {
+    /// Image embed `![alt](src)`
+    #[default]
+    Embed,
+    /// Only the alt text
+    AltText,
+}
+
+/// How link destinations are written
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LinkStyle {
+    /// `[text](https://example.com)`
+    #[default]
+    Inlined,
+    /// `[text][1]` with `[1]: https://example.com` at the end of the document
+    Referenced,
+}
+
+/// Attributes used by lazy-loading scripts to hold the real image URL
+const LAZY_SRC_ATTRIBUTES: [&str; 4] = ["data-src", "data-original", "data-lazy-src", "data-url"];
+
+/// Attributes holding a `srcset` value
+const SRCSET_ATTRIBUTES: [&str; 2] = ["srcset", "data-srcset"];
+
+/// Size descriptor of a `srcset` candidate
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Descriptor {
+    Width(f64),
+    Density(f64),
+}
+
+/// Parses a `srcset` value into URL and descriptor pairs
+fn parse_srcset(srcset: &str) -> Vec<(&str, Descriptor)> {
+    let mut candidates = Vec::new();
+    let mut rest = srcset;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+
+        // the URL runs until whitespace, a trailing comma ends the candidate
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let raw_url = &rest[..url_end];
+        let url = raw_url.trim_end_matches(',');
+        rest = &rest[url_end..];
+
+        let mut descriptor = Descriptor::Density(1.0);
+        if url.len() == raw_url.len() {
+            let descriptor_end = rest.find(',').unwrap_or(rest.len());
+            for token in rest[..descriptor_end].split_whitespace() {
+                if let Some(width) = token.strip_suffix('w').and_then(|w| w.parse().ok()) {
+                    descriptor = Descriptor::Width(width);
+                } else if let Some(density) = token.strip_suffix('x').and_then(|x| x.parse().ok()) {
+                    descriptor = Descriptor::Density(density);
+                }
+            }
+            rest = &rest[descriptor_end..];
+        }
+
+        if !url.is_empty() {
+            candidates.push((url, descriptor));
+        }
+    }
+    candidates
+}
+
+/// Picks the largest candidate of a `srcset` value
+fn best_srcset_candidate(srcset: &str) -> Option<&str> {
+    let candidates = parse_srcset(srcset);
+    let has_width = candidates
+        .iter()
+        .any(|(_, descriptor)| matches!(descriptor, Descriptor::Width(_)));
+
+    candidates
+        .into_iter()
+        .filter_map(|(url, descriptor)| match descriptor {
+            Descriptor::Width(size) if has_width => Some((url, size)),
+            Descriptor::Density(size) if !has_width => Some((url, size)),
+            _ => None,
+        })
+        .fold(None, |best: Option<(&str, f64)>, (url, size)| match best {
+            Some((_, best_size)) if best_size >= size => best,
+            _ => Some((url, size)),
+        })
+        .map(|(url, _)| url)
+}
+
+/// An iframe from a known service converted to its canonical page
+#[derive(Debug, PartialEq)]
+struct Embed {
+    service: &'static str,
+    url: String,
+    /// Obsidian previews the URL with `![](…)`
+    previewable: bool,
+}
+
+/// Splits a URL into lowercase host, path and query (scheme and fragment removed)
+fn split_url(url: &str) -> Option<(String, &str, &str)> {
+    let rest = url
+        .trim()
+        .strip_prefix("https://")
+        .or_else(|| url.trim().strip_prefix("http://"))
+        .or_else(|| url.trim().strip_prefix("//"))?;
+    let rest = rest.split('#').next().unwrap_or(rest);
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (host, path) = rest
+        .find('/')
+        .map_or((rest, ""), |slash| rest.split_at(slash));
+    Some((host.to_ascii_lowercase(), path, query))
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Converts YouTube, Vimeo, CodePen and Speaker Deck player URLs to their canonical page
+fn canonical_embed(src: &str) -> Option<Embed> {
+    let (host, path, query) = split_url(src)?;
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (host, segments.as_slice()) {
+        ("youtube.com" | "youtube-nocookie.com", ["embed", id, ..]) => {
+            let mut url = format!("https://www.youtube.com/watch?v={id}");
+            if let Some(start) = query_param(query, "start") {
+                url.push_str(&format!("&t={start}"));
+            }
+            Some(Embed {
+                service: "YouTube",
+                url,
+                previewable: true,
+            })
+        }
+        ("player.vimeo.com", ["video", id, ..]) => Some(Embed {
+            service: "Vimeo",
+            url: format!("https://vimeo.com/{id}"),
+            previewable: false,
+        }),
+        ("codepen.io", [user, "embed", rest @ ..]) => {
+            // /user/embed/ID and /user/embed/preview/ID
+            let id = rest.iter().rfind(|segment| **segment != "preview")?;
+            Some(Embed {
+                service: "CodePen",
+                url: format!("https://codepen.io/{user}/pen/{id}"),
+                previewable: false,
+            })
+        }
+        ("speakerdeck.com", ["player", id, ..]) => Some(Embed {
+            service: "Speaker Deck",
+            url: format!("https://speakerdeck.com/player/{id}"),
+            previewable: false,
+        }),
+        _ => None,
+    }
+}
+
 pub struct Media;
 
 impl Media {
-    /// Resolves a relative URL to an absolute URL using the base URL
-    fn resolve_url(&self, base_url: &str, url: &str) -> Result<String, ConvertError> {
-        if url.starts_with("https://")
-            || url.starts_with("mailto:")
-            || url.starts_with("tel:")
-            || url.starts_with("ftp:")
-        {
-            return Ok(url.to_string());
+    /// Destination part of a link, `(url)` or `[n]` with the URL collected for the reference list.
+    /// Identical targets share one reference.
+    pub fn link_destination(ctx: &mut Context, target: &str) -> String {
+        if ctx.options.link_style == LinkStyle::Inlined {
+            return format!("({target})");
         }
 
-        let clean_base = base_url.split('#').next().unwrap_or(base_url);
-        let clean_base = clean_base.split('?').next().unwrap_or(clean_base);
-
-        // extension check
-        let base_for_join = if self.has_file_extension(clean_base) {
-            let trimmed = clean_base
-                .rsplit_once('/')
-                .map(|(base, _)| format!("{base}/"))
-                .ok_or_else(|| {
-                    ConvertError::InvalidUrl(format!("File extension seems invalid {clean_base}"))
-                })?;
-            trimmed.to_string()
-        } else if clean_base.ends_with('/') {
-            clean_base.to_string()
-        } else {
-            format!("{clean_base}/")
+        let index = match ctx.link_references.iter().position(|url| url == target) {
+            Some(index) => index,
+            None => {
+                ctx.link_references.push(target.to_string());
+                ctx.link_references.len() - 1
+            }
         };
+        format!("[{}]", index + 1)
+    }
 
-        match self.url_join(&base_for_join, url) {
-            Ok(resolved) => Ok(resolved),
-            Err(_) => Ok(url.to_string()),
-        }
+    /// Reference definitions (`[1]: url`) for the links collected by `link_destination`
+    pub fn reference_definitions(ctx: &Context) -> String {
+        ctx.link_references
+            .iter()
+            .enumerate()
+            .map(|(i, url)| format!("[{}]: {url}\n", i + 1))
+            .collect()
     }
 
-    fn has_file_extension(&self, url: &str) -> bool {
-        // https:// included at lest two slashes
-        if url.matches('/').count() <= 2 {
+    /// Validates if the URL is safe to include in markdown
+    fn is_safe_url(&self, url: &str) -> bool {
+        let trimmed = url.trim();
+        if trimmed.is_empty() {
             return false;
         }
 
-        url.split('/')
-            .next_back()
-            .map(|segment| segment.contains('.') && segment.split('.').count() > 1)
-            .unwrap_or(false)
+        let lower_url = trimmed.to_lowercase();
+
+        !lower_url.starts_with("http://") // accept only https
+            && !lower_url.starts_with("#") // in-page anchors are not resources, `<a href="#id">` becomes a heading link
+            && !lower_url.starts_with("javascript:")
+            && !lower_url.starts_with("data:")
+            && !lower_url.starts_with("vbscript:")
     }
 
-    fn url_join(&self, base: &str, relative: &str) -> Result<String, ConvertError> {
-        let (host, path) = self.parse_url(base)?;
+    /// In-page `#fragment` link as an Obsidian heading link `[[#Heading text|label]]`,
+    /// only the label is kept when no heading has the `id`
+    fn render_fragment_link(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+        fragment: &str,
+    ) -> Result<String, ConvertError> {
+        // an image can't be the label of an Obsidian link, image links are kept as images
+        let target = if dom.find_element_by_tag(id, "img").is_some() {
+            None
+        } else {
+            Heading::link_target(dom, &percent_decode(fragment))
+        };
 
-        if relative.starts_with("/") {
-            Ok(format!("https://{host}{relative}"))
-        } else if relative.starts_with("./") {
-            let rel_path = relative.strip_prefix("./").ok_or_else(|| {
-                ConvertError::InvalidUrl(format!("Cannot strip ./ from {relative}"))
-            })?;
-            if path.ends_with('/') {
-                Ok(format!("https://{host}{path}{rel_path}"))
-            } else {
-                Ok(format!("https://{host}{path}/{rel_path}"))
+        let old_inline_status = ctx.in_inline;
+        let old_in_link_text = ctx.in_link_text;
+        ctx.in_inline = true;
+        ctx.in_link_text = target.is_some();
+        let label = render_children(url, dom, id, ctx)?;
+        ctx.in_inline = old_inline_status;
+        ctx.in_link_text = old_in_link_text;
+
+        match target {
+            Some(target) if !label.trim().is_empty() => {
+                // a bare '|' would split the table cell
+                let separator = if ctx.in_table { "\\|" } else { "|" };
+                Ok(format!("[[{target}{separator}{}]]", label.trim()))
             }
-        } else if relative.starts_with("../") {
-            self.resolve_parent_path(&host, &path, relative)
-        } else if path.ends_with('/') {
-            Ok(format!("https://{host}{path}{relative}"))
-        } else {
-            Ok(format!("https://{host}{path}/{relative}"))
+            _ => Ok(label),
         }
     }
 
-    fn parse_url(&self, url: &str) -> Result<(String, String), ConvertError> {
-        if !url.starts_with("https://") {
-            return Err(ConvertError::InvalidUrl(format!(
-                "Not HTTPS protocol: {url}"
-            )));
+    /// Best candidate from the <source> elements of a <picture>,
+    /// sources without a media query (the default) are preferred
+    fn picture_source(&self, dom: &Dom, picture_id: NodeId) -> Option<String> {
+        let mut sources: Vec<&HashMap<String, String>> = dom
+            .iter_children(picture_id)
+            .ok()?
+            .filter_map(|&child_id| {
+                dom.get_element_data(child_id)
+                    .ok()
+                    .filter(|(tag, _)| tag.local.as_ref() == "source")
+                    .map(|(_, attrs)| attrs)
+            })
+            .collect();
+        sources.sort_by_key(|attrs| attrs.contains_key("media"));
+
+        sources.into_iter().find_map(|attrs| {
+            SRCSET_ATTRIBUTES
+                .iter()
+                .filter_map(|name| attrs.get(*name))
+                .filter_map(|srcset| best_srcset_candidate(srcset))
+                .find(|url| self.is_safe_url(url))
+                .map(str::to_string)
+        })
+    }
+
+    /// Finds the URL of an image from <picture> sources, `srcset`,
+    /// lazy-loading attributes and finally `src`
+    fn image_source(&self, dom: &Dom, id: NodeId, attrs: &HashMap<String, String>) -> String {
+        let from_picture = dom
+            .get_parent(id)
+            .ok()
+            .flatten()
+            .filter(|&parent_id| {
+                dom.get_element_data(parent_id)
+                    .is_ok_and(|(tag, _)| tag.local.as_ref() == "picture")
+            })
+            .and_then(|picture_id| self.picture_source(dom, picture_id));
+        if let Some(url) = from_picture {
+            return url;
         }
 
-        let rest_url = url.strip_prefix("https://").ok_or_else(|| {
-            ConvertError::InvalidUrl(format!("Failed to strip https:// from {url}"))
-        })?;
-        let (host, path) = if let Some(slash_pos) = rest_url.find('/') {
-            (&rest_url[..slash_pos], &rest_url[slash_pos..])
-        } else {
-            (rest_url, "/")
-        };
-        Ok((host.to_string(), path.to_string()))
+        let from_srcset = SRCSET_ATTRIBUTES
+            .iter()
+            .filter_map(|name| attrs.get(*name))
+            .filter_map(|srcset| best_srcset_candidate(srcset))
+            .find(|url| self.is_safe_url(url));
+        if let Some(url) = from_srcset {
+            return url.to_string();
+        }
+
+        LAZY_SRC_ATTRIBUTES
+            .iter()
+            .chain(std::iter::once(&"src"))
+            .filter_map(|name| attrs.get(*name))
+            .find(|url| self.is_safe_url(url))
+            .or_else(|| attrs.get("src"))
+            .map(|url| url.trim().to_string())
+            .unwrap_or_default()
     }
 
-    fn resolve_parent_path(
+    /// Renders <figure> content followed by the <figcaption> as an italic line
+    fn render_figure(
         &self,
-        host: &str,
-        path: &str,
-        relative: &str,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
     ) -> Result<String, ConvertError> {
-        // split by '/' and collect as Vec
-        let mut path_parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-        let mut rel_parts: Vec<&str> = relative.split('/').collect();
-
-        while let Some(&part) = rel_parts.first() {
-            if part == ".." {
-                if !path_parts.is_empty() {
-                    path_parts.pop();
-                }
-                rel_parts.remove(0);
-            } else if part == "." {
-                rel_parts.remove(0);
+        let mut content = String::new();
+        let mut caption = String::new();
+
+        for &child_id in dom.iter_children(id)? {
+            let is_caption = dom
+                .get_element_data(child_id)
+                .is_ok_and(|(tag, _)| tag.local.as_ref() == "figcaption");
+
+            if is_caption {
+                let old_inline_status = ctx.in_inline;
+                ctx.in_inline = true;
+                let rendered = render_children(url, dom, child_id, ctx);
+                ctx.in_inline = old_inline_status;
+                caption.push_str(&rendered?);
             } else {
-                break;
+                content.push_str(&super::render_node(url, dom, child_id, ctx)?);
             }
         }
 
-        path_parts.extend(rel_parts.iter().filter(|s| !s.is_empty()));
+        let caption = caption.split_whitespace().collect::<Vec<_>>().join(" ");
+        let content = content.trim_end();
+        if caption.is_empty() {
+            return Ok(match (content.is_empty(), ctx.list_depth) {
+                (true, _) => String::new(),
+                (false, 0) => format!("{content}\n\n"),
+                (false, _) => content.to_string(),
+            });
+        }
 
-        let final_path = if path_parts.is_empty() {
-            "/".to_string()
+        let caption = format!("*{caption}*");
+        if content.is_empty() {
+            return Ok(format_list_content(ctx, &caption));
+        }
+        if ctx.list_depth > 0 {
+            let indent = " ".repeat(ctx.list_depth);
+            Ok(format!("{content}\n\n{indent}{caption}"))
         } else {
-            format!("/{}", path_parts.join("/"))
-        };
+            Ok(format!("{content}\n\n{caption}\n\n"))
+        }
+    }
 
-        Ok(format!("https://{host}{final_path}"))
+    /// Title of an embedded element from `title` or `aria-label`, escaped for link text
+    fn embed_title(attrs: &HashMap<String, String>) -> Option<String> {
+        ["title", "aria-label"]
+            .iter()
+            .filter_map(|name| attrs.get(*name))
+            .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+            .find(|title| !title.is_empty())
+            .map(|title| cow_to_string(escape_markdown(&title, EscapeContext::LinkText)))
     }
 
-    /// Validates if the URL is safe to include in markdown
-    fn is_safe_url(&self, url: &str) -> bool {
-        let trimmed = url.trim();
-        if trimmed.is_empty() {
-            return false;
+    /// Source of <video>/<audio> from `src`, lazy-loading attributes or the first <source>.
+    /// `blob:` URLs of streaming players only exist in the browser session.
+    fn media_source(
+        &self,
+        dom: &Dom,
+        id: NodeId,
+        attrs: &HashMap<String, String>,
+    ) -> Option<String> {
+        let is_playable = |src: &&String| self.is_safe_url(src) && !src.trim().starts_with("blob:");
+        let from_attrs = ["src", "data-src"]
+            .iter()
+            .filter_map(|name| attrs.get(*name))
+            .find(is_playable);
+        if let Some(src) = from_attrs {
+            return Some(src.trim().to_string());
         }
 
-        let lower_url = trimmed.to_lowercase();
+        dom.iter_children(id).ok()?.find_map(|&child_id| {
+            dom.get_element_data(child_id)
+                .ok()
+                .filter(|(tag, _)| tag.local.as_ref() == "source")
+                .and_then(|(_, attrs)| attrs.get("src"))
+                .filter(is_playable)
+                .map(|src| src.trim().to_string())
+        })
+    }
 
-        !lower_url.starts_with("http://") // accept only https
-            && !lower_url.starts_with("#") // anchor links are safe but not treated as links in markdown
-            && !lower_url.starts_with("javascript:")
-            && !lower_url.starts_with("data:")
-            && !lower_url.starts_with("vbscript:")
+    /// Renders <video>/<audio> as an embed Obsidian plays,
+    /// a video without a playable source falls back to its poster image
+    fn render_media_element(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let (_, attrs) = dom.get_element_data(id)?;
+        let title = Self::embed_title(attrs).unwrap_or_default();
+
+        let result = if let Some(src) = self.media_source(dom, id, attrs) {
+            format!("![{title}]({})", resolve_url(url, &src))
+        } else if let Some(poster) = attrs.get("poster").filter(|p| self.is_safe_url(p)) {
+            format!("![{title}]({})", resolve_url(url, poster.trim()))
+        } else {
+            return Ok(String::new());
+        };
+
+        if ctx.in_inline {
+            Ok(result)
+        } else {
+            Ok(format_list_content(ctx, &result))
+        }
+    }
+
+    /// Renders an <iframe> as a link to the embedded page,
+    /// YouTube players become `![](…)` embeds Obsidian previews
+    fn render_iframe(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let (_, attrs) = dom.get_element_data(id)?;
+        let Some(src) = ["src", "data-src"]
+            .iter()
+            .filter_map(|name| attrs.get(*name))
+            .find(|src| !src.trim().is_empty())
+        else {
+            return Ok(String::new());
+        };
+        let title = Self::embed_title(attrs);
+
+        let result = match canonical_embed(src) {
+            Some(embed) => {
+                let title = title.unwrap_or_else(|| embed.service.to_string());
+                if embed.previewable {
+                    format!("![{title}]({})", embed.url)
+                } else {
+                    format!("[{title}]{}", Self::link_destination(ctx, &embed.url))
+                }
+            }
+            None => {
+                // protocol-relative URLs are used by many embed snippets
+                let src = match src.trim().strip_prefix("//") {
+                    Some(rest) => format!("https://{rest}"),
+                    None => src.trim().to_string(),
+                };
+                if !self.is_safe_url(&src) || src.starts_with("about:") {
+                    return Ok(String::new());
+                }
+                let resolved = resolve_url(url, &src);
+                let title = title.unwrap_or_else(|| {
+                    split_url(&resolved).map_or_else(|| resolved.clone(), |(host, _, _)| host)
+                });
+                format!("[{title}]{}", Self::link_destination(ctx, &resolved))
+            }
+        };
+
+        if ctx.in_inline {
+            Ok(result)
+        } else {
+            Ok(format_list_content(ctx, &result))
+        }
     }
 
     /// Extracts and cleans alt text for images
@@ -203,7 +540,14 @@ impl Renderer for Media {
         };
 
         if let NodeData::Element { tag, .. } = &node.data {
-            matches!(tag.local.as_ref(), "a" | "img")
+            match tag.local.as_ref() {
+                // footnote references and backlinks are rendered by the footnote renderer
+                "a" => !FOOTNOTE.matches(dom, id),
+                "img" | "picture" | "video" | "audio" | "iframe" => true,
+                // <figure data-diagram-source> is rendered by the diagram renderer
+                "figure" => !DIAGRAM.matches(dom, id),
+                _ => false,
+            }
         } else {
             false
         }
@@ -221,10 +565,22 @@ impl Renderer for Media {
 
         match tag.local.as_ref() {
             "a" => {
+                // permalink icons like <a class="anchor" href="#id">#</a> would leak into the title
+                if ctx.in_heading && Heading::is_permalink(dom, id) {
+                    return Ok(String::new());
+                }
+
+                if let Some(fragment) = attrs
+                    .get("href")
+                    .and_then(|href| href.trim().strip_prefix('#'))
+                {
+                    return self.render_fragment_link(url, dom, id, ctx, fragment);
+                }
+
                 if let Some(href) = attrs.get("href")
                     && self.is_safe_url(href)
                 {
-                    let resolved_url = self.resolve_url(url, href)?;
+                    let resolved_url = resolve_url(url, href);
 
                     // in case of complex links, like bookmark, example:
                     // <a href="https://example.com/path?query#fragment">
@@ -236,11 +592,17 @@ impl Renderer for Media {
                         return self.render_complex_link(url, dom, id, ctx, resolved_url);
                     }
 
+                    let old_in_link_text = ctx.in_link_text;
                     ctx.in_inline = true;
+                    ctx.in_link_text = true;
                     let content = render_children(url, dom, id, ctx)?;
                     ctx.in_inline = old_inline_status;
+                    ctx.in_link_text = old_in_link_text;
 
-                    Ok(format!("[{content}]({resolved_url})"))
+                    Ok(format!(
+                        "[{content}]{}",
+                        Self::link_destination(ctx, &resolved_url)
+                    ))
                 } else {
                     ctx.in_inline = true;
                     let content = render_children(url, dom, id, ctx)?;
@@ -249,23 +611,24 @@ impl Renderer for Media {
                 }
             }
             "img" => {
-                if ctx.in_heading {
+                if ctx.in_heading || ctx.options.images == ImageStyle::AltText {
                     return Ok(self.get_alt_text(attrs));
                 }
 
                 let alt = self.get_alt_text(attrs);
-                let src = attrs.get("src").unwrap_or(&String::new()).clone();
+                let src = self.image_source(dom, id, attrs);
 
                 // check link context
-                let result = if let Some(link_info) = &ctx.link_info {
+                let result = if let Some(link_info) = ctx.link_info.clone() {
+                    let destination = Self::link_destination(ctx, &link_info);
                     if self.is_safe_url(&src) {
-                        let resolved_src = self.resolve_url(url, &src)?;
-                        format!("[![{alt}]({resolved_src})]({link_info})",)
+                        let resolved_src = resolve_url(url, &src);
+                        format!("[![{alt}]({resolved_src})]{destination}")
                     } else {
-                        format!("[{alt}]({link_info})")
+                        format!("[{alt}]{destination}")
                     }
                 } else if self.is_safe_url(&src) {
-                    let resolved_src = self.resolve_url(url, &src)?;
+                    let resolved_src = resolve_url(url, &src);
                     format!("![{alt}]({resolved_src})")
                 } else {
                     alt
@@ -277,6 +640,10 @@ impl Renderer for Media {
                     Ok(format_list_content(ctx, &result))
                 }
             }
+            "figure" => self.render_figure(url, dom, id, ctx),
+            "video" | "audio" => self.render_media_element(url, dom, id, ctx),
+            "iframe" => self.render_iframe(url, dom, id, ctx),
+            // <picture> is rendered through its <img>, which picks the best <source>
             _ => render_children(url, dom, id, ctx),
         }
     }
@@ -287,268 +654,90 @@ pub static MEDIA: Media = Media;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::ConvertOptions;
     use crate::parser;
     use crate::renderers;
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
 
-    /// resolve url test from root directory
-    #[rstest]
-    #[case(
-        "https://example.com",
-        "/assets/logo.png",
-        "https://example.com/assets/logo.png"
-    )]
-    #[case(
-        "https://example.com/",
-        "/css/style.css",
-        "https://example.com/css/style.css"
-    )]
-    #[case(
-        "https://blog.example.com/subpage",
-        "/images/header.jpg",
-        "https://blog.example.com/images/header.jpg"
-    )]
-    #[case(
-        "https://blog.example.com/tags/",
-        "/category/index.html",
-        "https://blog.example.com/category/index.html"
-    )]
-    #[case(
-        "https://blog.example.com/deep/nested/path",
-        "/from/root/index.html",
-        "https://blog.example.com/from/root/index.html"
-    )]
-    #[case(
-        "https://blog.example.com/file/index.html",
-        "/root/index.html",
-        "https://blog.example.com/root/index.html"
-    )]
-    fn test_root_relative_urls(
-        #[case] base_url: &str,
-        #[case] relative_url: &str,
-        #[case] expected: &str,
-    ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, relative_url).unwrap(), expected);
-    }
-
-    /// relative URL path test
-    #[rstest]
-    #[case(
-        "https://example.com/blog",
-        "image.jpg",
-        "https://example.com/blog/image.jpg"
-    )]
-    #[case(
-        "https://example.com/blog/",
-        "thumbnail.png",
-        "https://example.com/blog/thumbnail.png"
-    )]
-    #[case(
-        "https://example.com/docs/api",
-        "diagram.svg",
-        "https://example.com/docs/api/diagram.svg"
-    )]
-    #[case(
-        "https://example.com/category/subcategory",
-        "images/icon.gif",
-        "https://example.com/category/subcategory/images/icon.gif"
-    )]
-    #[case(
-        "https://example.com/category/subcategory/index.html",
-        "images/icon.gif",
-        "https://example.com/category/subcategory/images/icon.gif"
-    )]
-    fn test_relative_urls_from_subpages(
-        #[case] base_url: &str,
-        #[case] relative_url: &str,
-        #[case] expected: &str,
-    ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, relative_url).unwrap(), expected);
-    }
-
-    /// relative URL from current directory test
-    #[rstest]
-    #[case(
-        "https://example.com/blog",
-        "./image.jpg",
-        "https://example.com/blog/image.jpg"
-    )]
-    #[case(
-        "https://example.com/docs/",
-        "./assets/diagram.png",
-        "https://example.com/docs/assets/diagram.png"
-    )]
-    #[case(
-        "https://example.com/docs/index.html",
-        "./assets/diagram.png",
-        "https://example.com/docs/assets/diagram.png"
-    )]
-    fn test_current_directory_relative_urls(
-        #[case] base_url: &str,
-        #[case] relative_url: &str,
-        #[case] expected: &str,
-    ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, relative_url).unwrap(), expected);
-    }
-
-    /// upstream relative URL test
+    /// anchor links test
     #[rstest]
     #[case(
-        "https://example.com/blog/post",
-        "../assets/image.jpg",
-        "https://example.com/blog/assets/image.jpg"
-    )]
-    #[case(
-        "https://example.com/docs/api/",
-        "../images/logo.png",
-        "https://example.com/docs/images/logo.png"
-    )]
-    #[case(
-        "https://example.com/a/b/c",
-        "../../shared/icon.svg",
-        "https://example.com/a/shared/icon.svg"
-    )]
-    #[case(
-        "https://example.com/deep/nested/path",
-        "../../../root.jpg",
-        "https://example.com/root.jpg"
+        r##"<a href="#introduction">Introduction</a>"##,
+        "https://example.com/guide",
+        "Introduction"
     )]
     #[case(
-        "https://example.com/a/b/index.html",
-        "../c/root.jpg",
-        "https://example.com/a/c/root.jpg"
-    )]
-    fn test_parent_directory_relative_urls(
-        #[case] base_url: &str,
-        #[case] relative_url: &str,
-        #[case] expected: &str,
-    ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, relative_url).unwrap(), expected);
-    }
-
-    /// absolute URL passthrough test
-    #[rstest]
-    #[case(
-        "https://example.com",
-        "https://cdn.example.com/image.jpg",
-        "https://cdn.example.com/image.jpg"
+        r##"<a href="#section-1">Go to Section 1</a>"##,
+        "https://docs.example.com/api",
+        "Go to Section 1"
     )]
     #[case(
-        "https://example.com",
-        "https://external-site.com/api/data",
-        "https://external-site.com/api/data"
+        r##"<a href="#top">Back to Top</a>"##,
+        "https://blog.example.com/post/123",
+        "Back to Top"
     )]
-    fn test_absolute_urls_passthrough(
+    fn test_internal_anchor_links(
+        #[case] html: &str,
         #[case] base_url: &str,
-        #[case] absolute_url: &str,
         #[case] expected: &str,
     ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, absolute_url).unwrap(), expected);
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node(base_url, &dom, dom.document, &mut context)
+            .expect("Failed to render internal anchor link");
+        assert_eq!(result, expected);
     }
 
-    /// special schemes test
+    /// in-page anchor links to headings become Obsidian heading links
     #[rstest]
     #[case(
-        "https://example.com",
-        "mailto:contact@example.com",
-        "mailto:contact@example.com"
+        r##"<h2 id="intro">Introduction</h2><p>See <a href="#intro">the intro</a></p>"##,
+        "## Introduction\n\nSee [[#Introduction|the intro]]\n\n"
     )]
-    #[case("https://example.com", "tel:+1234567890", "tel:+1234567890")]
     #[case(
-        "https://example.com",
-        "ftp://files.example.com/doc.pdf",
-        "ftp://files.example.com/doc.pdf"
+        r##"<h2 id="setup">Step 1: <code>cargo</code> setup</h2><p><a href="#setup">Setup</a></p>"##,
+        "## Step 1: `cargo` setup\n\n[[#Step 1 cargo setup|Setup]]\n\n"
     )]
-    fn test_special_schemes(#[case] base_url: &str, #[case] url: &str, #[case] expected: &str) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, url).unwrap(), expected);
-    }
-
-    /// query parameters and fragments test
-    #[rstest]
     #[case(
-        "https://example.com/search?q=test",
-        "/assets/logo.png",
-        "https://example.com/assets/logo.png"
+        r##"<h3 id="usage"><a class="anchor" href="#usage">#</a>Usage</h3><p><a href="#usage">usage</a></p>"##,
+        "### Usage\n\n[[#Usage|usage]]\n\n"
     )]
     #[case(
-        "https://example.com/page#section",
-        "image.jpg",
-        "https://example.com/page/image.jpg"
+        r##"<h2><a id="faq"></a>FAQ</h2><p><a href="#faq">FAQ</a></p>"##,
+        "## FAQ\n\n[[#FAQ|FAQ]]\n\n"
     )]
     #[case(
-        "https://example.com/blog?page=2&sort=date",
-        "../images/header.jpg",
-        "https://example.com/images/header.jpg"
+        r##"<h2><a name="old-style"></a>Old style</h2><p><a href="#old-style">here</a></p>"##,
+        "## Old style\n\n[[#Old style|here]]\n\n"
     )]
-    fn test_base_url_with_query_and_fragment(
-        #[case] base_url: &str,
-        #[case] relative_url: &str,
-        #[case] expected: &str,
-    ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, relative_url).unwrap(), expected);
-    }
-
-    /// file-based relative URLs test
-    #[rstest]
     #[case(
-        "https://example.com/a/index.html",
-        "../b/image.png",
-        "https://example.com/b/image.png"
+        r##"<h2 id="概要">概要</h2><p><a href="#%E6%A6%82%E8%A6%81">概要へ</a></p>"##,
+        "## 概要\n\n[[#概要|概要へ]]\n\n"
     )]
     #[case(
-        "https://example.com/docs/guide.html",
-        "../assets/logo.svg",
-        "https://example.com/assets/logo.svg"
+        r##"<h2 id="api">API</h2><table><tr><th>Go</th></tr><tr><td><a href="#api">API</a></td></tr></table>"##,
+        "## API\n\n| Go |\n| --- |\n| [[#API\\|API]] |\n\n"
     )]
     #[case(
-        "https://example.com/blog/posts/article.html",
-        "../../images/header.jpg",
-        "https://example.com/images/header.jpg"
+        r##"<p id="note">A note</p><p><a href="#note">note</a> and <a href="#missing">missing</a></p>"##,
+        "A note\n\nnote and missing\n\n"
     )]
-    fn test_file_based_relative_urls(
-        #[case] base_url: &str,
-        #[case] relative_url: &str,
-        #[case] expected: &str,
-    ) {
-        let media = Media;
-        assert_eq!(media.resolve_url(base_url, relative_url).unwrap(), expected);
-    }
-
-    /// anchor links test
-    #[rstest]
     #[case(
-        r##"<a href="#introduction">Introduction</a>"##,
-        "https://example.com/guide",
-        "Introduction"
+        r##"<h2 id="top">Top</h2><p><a href="#">Empty</a></p>"##,
+        "## Top\n\nEmpty\n\n"
     )]
     #[case(
-        r##"<a href="#section-1">Go to Section 1</a>"##,
-        "https://docs.example.com/api",
-        "Go to Section 1"
+        r##"<h2 id="gallery">Gallery</h2><p><a href="#gallery"><img src="pic.png" alt="Pic"></a></p>"##,
+        "## Gallery\n\n![Pic](https://example.com/pic.png)\n\n"
     )]
-    #[case(
-        r##"<a href="#top">Back to Top</a>"##,
-        "https://blog.example.com/post/123",
-        "Back to Top"
-    )]
-    fn test_internal_anchor_links(
-        #[case] html: &str,
-        #[case] base_url: &str,
-        #[case] expected: &str,
-    ) {
+    fn test_fragment_links(#[case] html: &str, #[case] expected: &str) {
         let dom = parser::parse_html(html).expect("Failed to parse HTML");
         let mut context = Context::default();
-        let result = renderers::render_node(base_url, &dom, dom.document, &mut context)
-            .expect("Failed to render internal anchor link");
+        let result =
+            renderers::render_node("https://example.com", &dom, dom.document, &mut context)
+                .expect("Failed to render fragment link");
         assert_eq!(result, expected);
     }
 
@@ -615,9 +804,11 @@ mod tests {
         r#"<figure><img src="../images/chart.svg" alt="Performance Chart"><figcaption>Q4 Performance</figcaption></figure>"#,
         "https://example.com/reports/2024",
         indoc! {r#"
-            ![Performance Chart](https://example.com/reports/images/chart.svg)
+            ![Performance Chart](https://example.com/images/chart.svg)
+
+            *Q4 Performance*
 
-            Q4 Performance"#}
+            "#}
     )]
     fn test_article_images(#[case] html: &str, #[case] base_url: &str, #[case] expected: &str) {
         let dom = parser::parse_html(html).expect("Failed to parse HTML");
@@ -627,6 +818,190 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    /// srcset candidate selection
+    #[rstest]
+    #[case("a.jpg 480w, b.jpg 1080w, c.jpg 800w", Some("b.jpg"))]
+    #[case("a.jpg, b.jpg 2x, c.jpg 1.5x", Some("b.jpg"))]
+    #[case(
+        "https://cdn.example.com/w_100,h_100/img.jpg 100w, https://cdn.example.com/w_200,h_200/img.jpg 200w",
+        Some("https://cdn.example.com/w_200,h_200/img.jpg")
+    )]
+    #[case("single.jpg", Some("single.jpg"))]
+    #[case("a.jpg 1x,b.jpg 2x", Some("b.jpg"))]
+    #[case("", None)]
+    fn test_best_srcset_candidate(#[case] srcset: &str, #[case] expected: Option<&str>) {
+        assert_eq!(best_srcset_candidate(srcset), expected);
+    }
+
+    /// image source selection from srcset, <picture> and lazy-loading attributes
+    #[rstest]
+    #[case(
+        r#"<img src="/small.jpg" srcset="/small.jpg 400w, /large.jpg 1200w" alt="Photo">"#,
+        "![Photo](https://example.com/large.jpg)\n\n"
+    )]
+    #[case(
+        r#"<img src="data:image/gif;base64,R0lGOD" data-src="/lazy.png" alt="Lazy">"#,
+        "![Lazy](https://example.com/lazy.png)\n\n"
+    )]
+    #[case(
+        r#"<img class="lazyload" data-original="https://cdn.example.com/real.jpg" alt="Original">"#,
+        "![Original](https://cdn.example.com/real.jpg)\n\n"
+    )]
+    #[case(
+        r#"<img src="data:image/gif;base64,R0lGOD" data-srcset="/a.jpg 1x, /b.jpg 2x" alt="Lazy set">"#,
+        "![Lazy set](https://example.com/b.jpg)\n\n"
+    )]
+    #[case(
+        r#"<picture><source media="(max-width: 600px)" srcset="/mobile.webp"><source type="image/avif" srcset="/photo.avif 1x, /photo@2x.avif 2x"><img src="/photo.jpg" alt="Picture"></picture>"#,
+        "![Picture](https://example.com/photo@2x.avif)\n\n"
+    )]
+    #[case(
+        r#"<picture><img src="/fallback.jpg" alt="Fallback"></picture>"#,
+        "![Fallback](https://example.com/fallback.jpg)\n\n"
+    )]
+    #[case(
+        r#"<img src="data:image/gif;base64,R0lGOD" alt="Placeholder only">"#,
+        "Placeholder only\n\n"
+    )]
+    fn test_image_sources(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result =
+            renderers::render_node("https://example.com", &dom, dom.document, &mut context)
+                .expect("Failed to render image");
+        assert_eq!(result, expected);
+    }
+
+    /// figure and figcaption
+    #[rstest]
+    #[case(
+        r#"<figure><img src="/cat.jpg" alt="Cat"><figcaption>A <em>very</em> sleepy cat</figcaption></figure>"#,
+        indoc! {r#"
+            ![Cat](https://example.com/cat.jpg)
+
+            *A *very* sleepy cat*
+
+            "#}
+    )]
+    #[case(
+        r#"<figure><figcaption>Caption first</figcaption><img src="/dog.jpg" alt="Dog"></figure>"#,
+        indoc! {r#"
+            ![Dog](https://example.com/dog.jpg)
+
+            *Caption first*
+
+            "#}
+    )]
+    #[case(
+        r#"<figure><img src="/no-caption.jpg" alt="Plain"></figure>"#,
+        "![Plain](https://example.com/no-caption.jpg)\n\n"
+    )]
+    #[case(
+        r#"<figure><img src="/a.jpg" alt="A"><figcaption>  </figcaption></figure>"#,
+        "![A](https://example.com/a.jpg)\n\n"
+    )]
+    #[case(
+        r#"<ul><li>Item<figure><img src="/b.jpg" alt="B"><figcaption>Nested caption</figcaption></figure></li></ul>"#,
+        indoc! {r#"
+            - Item
+
+              ![B](https://example.com/b.jpg)
+
+              *Nested caption*
+
+            "#}
+    )]
+    #[case(
+        r#"<figure><blockquote><p>Quote</p></blockquote><figcaption>Author</figcaption></figure>"#,
+        indoc! {r#"
+            > Quote
+
+            *Author*
+
+            "#}
+    )]
+    fn test_figures(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result =
+            renderers::render_node("https://example.com", &dom, dom.document, &mut context)
+                .expect("Failed to render figure");
+        assert_eq!(result, expected);
+    }
+
+    /// video and audio embeds
+    #[rstest]
+    #[case(
+        r#"<video src="/media/demo.mp4" title="Demo" controls>Your browser does not support video.</video>"#,
+        "![Demo](https://example.com/media/demo.mp4)\n\n"
+    )]
+    #[case(
+        r#"<video controls poster="/poster.jpg"><source src="/clip.webm" type="video/webm"><source src="/clip.mp4" type="video/mp4"></video>"#,
+        "![](https://example.com/clip.webm)\n\n"
+    )]
+    #[case(
+        r#"<video poster="/poster.jpg" src="blob:https://example.com/1234"></video>"#,
+        "![](https://example.com/poster.jpg)\n\n"
+    )]
+    #[case(
+        r#"<video poster="https://cdn.example.com/poster.jpg"></video>"#,
+        "![](https://cdn.example.com/poster.jpg)\n\n"
+    )]
+    #[case(
+        r#"<audio controls aria-label="Episode [1]"><source src="https://cdn.example.com/ep1.mp3" type="audio/mpeg"></audio>"#,
+        "![Episode \\[1\\]](https://cdn.example.com/ep1.mp3)\n\n"
+    )]
+    #[case(r#"<audio controls>No source</audio>"#, "")]
+    #[case(
+        r#"<p>Listen: <audio src="/a.mp3"></audio></p>"#,
+        "Listen: ![](https://example.com/a.mp3)\n\n"
+    )]
+    fn test_media_elements(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result =
+            renderers::render_node("https://example.com", &dom, dom.document, &mut context)
+                .expect("Failed to render media element");
+        assert_eq!(result, expected);
+    }
+
+    /// iframe embeds
+    #[rstest]
+    #[case(
+        r#"<iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ?start=42" title="Never Gonna Give You Up"></iframe>"#,
+        "![Never Gonna Give You Up](https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42)\n\n"
+    )]
+    #[case(
+        r#"<iframe src="https://www.youtube-nocookie.com/embed/abc123"></iframe>"#,
+        "![YouTube](https://www.youtube.com/watch?v=abc123)\n\n"
+    )]
+    #[case(
+        r#"<iframe src="https://player.vimeo.com/video/76979871?h=8272103f6e"></iframe>"#,
+        "[Vimeo](https://vimeo.com/76979871)\n\n"
+    )]
+    #[case(
+        r#"<iframe src="https://codepen.io/someone/embed/preview/abcDEF?default-tab=result" title="CSS Grid"></iframe>"#,
+        "[CSS Grid](https://codepen.io/someone/pen/abcDEF)\n\n"
+    )]
+    #[case(
+        r#"<iframe src="//speakerdeck.com/player/0123456789abcdef" title="Slides"></iframe>"#,
+        "[Slides](https://speakerdeck.com/player/0123456789abcdef)\n\n"
+    )]
+    #[case(
+        r#"<iframe src="https://maps.example.org/embed?q=tokyo"></iframe>"#,
+        "[maps.example.org](https://maps.example.org/embed?q=tokyo)\n\n"
+    )]
+    #[case(r#"<iframe src="javascript:alert(1)"></iframe>"#, "")]
+    #[case(r#"<iframe></iframe>"#, "")]
+    fn test_iframes(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result =
+            renderers::render_node("https://example.com", &dom, dom.document, &mut context)
+                .expect("Failed to render iframe");
+        assert_eq!(result, expected);
+    }
+
     /// CDN images test
     #[rstest]
     #[case(
@@ -786,7 +1161,7 @@ mod tests {
 
             Rust is becoming popular for web development. Here's why:
 
-            ![Rust Programming Language Logo](https://blog.example.com/posts/assets/rust-logo.png)
+            ![Rust Programming Language Logo](https://blog.example.com/assets/rust-logo.png)
 
             For more information, visit the [official Rust website](https://www.rust-lang.org/).
 
@@ -837,4 +1212,49 @@ mod tests {
                 .expect("Failed to render list with media");
         assert_eq!(result, expected);
     }
+
+    /// reference-style links
+    #[rstest]
+    #[case(
+        r#"<p>Read <a href="/docs?page=1&amp;lang=en">the docs</a> and <a href="https://example.org/">Example</a>, then <a href="/docs?page=1&amp;lang=en">the docs again</a>.</p>"#,
+        indoc! {r#"
+            Read [the docs][1] and [Example][2], then [the docs again][1].
+
+            [1]: https://example.com/docs?page=1&lang=en
+            [2]: https://example.org/
+            "#}
+    )]
+    #[case(
+        r#"<a href="/product"><img src="/thumb.jpg" alt="Thumb"><h3>Product</h3></a>"#,
+        indoc! {r#"
+            [![Thumb](https://example.com/thumb.jpg)][1]
+
+            ### [Product][1]
+
+            [1]: https://example.com/product
+            "#}
+    )]
+    #[case(
+        r#"<img src="/a.png" alt="Chart"><iframe src="https://player.vimeo.com/video/76979871"></iframe>"#,
+        indoc! {r#"
+            ![Chart](https://example.com/a.png)
+
+            [Vimeo][1]
+
+            [1]: https://vimeo.com/76979871
+            "#}
+    )]
+    #[case(r#"<p>No links</p>"#, "No links\n\n")]
+    fn test_reference_links(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().link_style(LinkStyle::Referenced),
+            ..Default::default()
+        };
+        let mut result =
+            renderers::render_node("https://example.com", &dom, dom.document, &mut context)
+                .expect("Failed to render links");
+        result.push_str(&Media::reference_definitions(&context));
+        assert_eq!(result, expected);
+    }
 }
@@ -33,7 +33,8 @@ impl Renderer for Paragraph {
         }
 
         // Handle the first item in a list differently
-        Ok(format_list_content(ctx, &content))
+        // (trailing newlines come from block content like display math)
+        Ok(format_list_content(ctx, content.trim_end()))
     }
 }
 
@@ -0,0 +1,167 @@
+use super::{Context, Renderer, render_children, render_node};
+use crate::{
+    dom::{Dom, NodeData, NodeId},
+    error::ConvertError,
+};
+
+/// How ruby annotations (furigana) are emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RubyMode {
+    /// Base text followed by the annotation in parentheses: `漢字(かんじ)`
+    #[default]
+    Parenthesized,
+    /// Only the base text: `漢字`
+    BaseOnly,
+    /// Keep the `<ruby>` element as HTML, which Obsidian renders
+    Html,
+}
+
+/// Represents a ruby renderer for `<ruby>漢字<rt>かんじ</rt></ruby>`.
+///
+/// `<rp>` fallback parentheses are dropped, the parenthesized mode adds its own.
+pub struct Ruby;
+
+impl Ruby {
+    fn tag_name(dom: &Dom, id: NodeId) -> Option<&str> {
+        dom.get_element_data(id)
+            .ok()
+            .map(|(tag, _)| tag.local.as_ref())
+    }
+
+    /// Renders the base/annotation pairs, an annotation applies to the base text before it
+    fn render_pairs(
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let mut result = String::new();
+        let mut base = String::new();
+
+        for &child_id in dom.iter_children(id)? {
+            match Self::tag_name(dom, child_id) {
+                Some("rp") => {}
+                Some("rt" | "rtc") => {
+                    let annotation = render_children(url, dom, child_id, ctx)?;
+                    let annotation = annotation.trim();
+                    result.push_str(base.trim());
+                    if ctx.options.ruby == RubyMode::Parenthesized && !annotation.is_empty() {
+                        result.push('(');
+                        result.push_str(annotation);
+                        result.push(')');
+                    }
+                    base.clear();
+                }
+                // <rb> and nested markup are part of the base text
+                _ => base.push_str(&render_node(url, dom, child_id, ctx)?),
+            }
+        }
+
+        result.push_str(base.trim());
+        Ok(result)
+    }
+}
+
+impl Renderer for Ruby {
+    fn matches(&self, dom: &Dom, id: NodeId) -> bool {
+        let Some(node) = dom.node(id) else {
+            return false;
+        };
+
+        if let NodeData::Element { tag, .. } = &node.data {
+            tag.local.as_ref() == "ruby"
+        } else {
+            false
+        }
+    }
+
+    fn render(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let result = if ctx.options.ruby == RubyMode::Html {
+            dom.outer_html(id)
+        } else {
+            Self::render_pairs(url, dom, id, ctx)?
+        };
+
+        ctx.last_char = result.chars().last();
+        Ok(result)
+    }
+}
+
+pub static RUBY: Ruby = Ruby;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::ConvertOptions;
+    use crate::parser;
+    use crate::renderers;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// default parenthesized mode
+    #[rstest]
+    #[case("<ruby>漢字<rt>かんじ</rt></ruby>", "漢字(かんじ)")]
+    #[case("<ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>", "漢字(かんじ)")]
+    #[case(
+        "<ruby>漢字<rp>（</rp><rt>かんじ</rt><rp>）</rp></ruby>",
+        "漢字(かんじ)"
+    )]
+    #[case(
+        "<ruby>漢<rp>(</rp><rt>かん</rt><rp>)</rp>字<rp>(</rp><rt>じ</rt><rp>)</rp></ruby>",
+        "漢(かん)字(じ)"
+    )]
+    #[case("<ruby><rb>東京</rb><rt>とうきょう</rt></ruby>", "東京(とうきょう)")]
+    #[case("<ruby>漢字<rt></rt></ruby>", "漢字")]
+    #[case("<ruby>漢字</ruby>", "漢字")]
+    #[case("<ruby><b>強</b><rt>つよ</rt></ruby>い", "**強**(つよ)い")]
+    #[case(
+        "<p>日本の<ruby>首都<rt>しゅと</rt></ruby>は<ruby>東京<rt>とうきょう</rt></ruby>です。</p>",
+        "日本の首都(しゅと)は東京(とうきょう)です。\n\n"
+    )]
+    fn test_parenthesized_ruby(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render ruby");
+        assert_eq!(result, expected);
+    }
+
+    /// base text only and HTML passthrough
+    #[rstest]
+    #[case(
+        "<ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>",
+        RubyMode::BaseOnly,
+        "漢字"
+    )]
+    #[case(
+        "<p><ruby>漢<rt>かん</rt>字<rt>じ</rt></ruby>を読む</p>",
+        RubyMode::BaseOnly,
+        "漢字を読む\n\n"
+    )]
+    #[case(
+        "<ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>",
+        RubyMode::Html,
+        "<ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>"
+    )]
+    #[case(
+        "<p>The <ruby>漢字<rt>kanji</rt></ruby> script</p>",
+        RubyMode::Html,
+        "The <ruby>漢字<rt>kanji</rt></ruby> script\n\n"
+    )]
+    fn test_ruby_modes(#[case] html: &str, #[case] mode: RubyMode, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context {
+            options: ConvertOptions::default().ruby(mode),
+            ..Default::default()
+        };
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render ruby");
+        assert_eq!(result, expected);
+    }
+}
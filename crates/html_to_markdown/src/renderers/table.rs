@@ -3,9 +3,336 @@ use crate::{
     dom::{Dom, NodeData, NodeId},
     error::ConvertError,
 };
+use std::collections::HashMap;
 
+/// Elements that cannot be expressed inside a single-line GFM table cell.
+const BLOCK_CONTENT_TAGS: &[&str] = &[
+    "ul",
+    "ol",
+    "pre",
+    "table",
+    "blockquote",
+    "dl",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+];
+
+/// Upper bound for `colspan`/`rowspan` to protect against malformed attributes.
+const MAX_SPAN: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn from_attrs(attrs: &HashMap<String, String>) -> Self {
+        let value = attrs.get("align").cloned().or_else(|| {
+            attrs.get("style").and_then(|style| {
+                style.split(';').find_map(|declaration| {
+                    let (property, value) = declaration.split_once(':')?;
+                    property
+                        .trim()
+                        .eq_ignore_ascii_case("text-align")
+                        .then(|| value.trim().to_string())
+                })
+            })
+        });
+
+        match value.map(|v| v.to_ascii_lowercase()).as_deref() {
+            Some("left" | "start") => Alignment::Left,
+            Some("center") => Alignment::Center,
+            Some("right" | "end") => Alignment::Right,
+            _ => Alignment::None,
+        }
+    }
+
+    const fn delimiter(self) -> &'static str {
+        match self {
+            Alignment::None => "---",
+            Alignment::Left => ":---",
+            Alignment::Center => ":---:",
+            Alignment::Right => "---:",
+        }
+    }
+}
+
+/// A rendered cell placed in the table grid
+#[derive(Debug, Clone)]
+struct Cell {
+    content: String,
+    alignment: Alignment,
+}
+
+impl Cell {
+    fn padding() -> Self {
+        Cell {
+            content: String::new(),
+            alignment: Alignment::None,
+        }
+    }
+}
+
+/// Represents a table renderer that converts <table> into a GFM table.
+/// Tables whose cells hold block content are kept as raw HTML.
 pub struct Table;
 
+impl Table {
+    /// Collects <tr> elements in document order, looking through <thead>, <tbody> and <tfoot>
+    fn collect_rows(dom: &Dom, table_id: NodeId) -> Vec<NodeId> {
+        let mut rows = Vec::new();
+        let Ok(children) = dom.iter_children(table_id) else {
+            return rows;
+        };
+
+        for &child_id in children {
+            let Ok((tag, _)) = dom.get_element_data(child_id) else {
+                continue;
+            };
+            match tag.local.as_ref() {
+                "tr" => rows.push(child_id),
+                "thead" | "tbody" | "tfoot" => rows.extend(Self::collect_rows(dom, child_id)),
+                _ => {}
+            }
+        }
+        rows
+    }
+
+    fn collect_cells(dom: &Dom, row_id: NodeId) -> Vec<NodeId> {
+        dom.iter_children(row_id)
+            .map(|children| {
+                children
+                    .copied()
+                    .filter(|&child_id| {
+                        dom.get_element_data(child_id)
+                            .is_ok_and(|(tag, _)| matches!(tag.local.as_ref(), "th" | "td"))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn has_block_content(dom: &Dom, id: NodeId) -> bool {
+        dom.iter_children(id).is_ok_and(|mut children| {
+            children.any(
+                |&child_id| match dom.node(child_id).map(|node| &node.data) {
+                    Some(NodeData::Element { tag, .. }) => {
+                        BLOCK_CONTENT_TAGS.contains(&tag.local.as_ref())
+                            || Self::has_block_content(dom, child_id)
+                    }
+                    _ => false,
+                },
+            )
+        })
+    }
+
+    fn span(attrs: &HashMap<String, String>, name: &str) -> usize {
+        attrs
+            .get(name)
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|&span| span > 0)
+            .map_or(1, |span| span.min(MAX_SPAN))
+    }
+
+    /// Flattens rendered cell content into a single line and escapes pipes
+    fn format_cell_content(content: &str) -> String {
+        let single_line = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("<br>");
+
+        let mut escaped = String::with_capacity(single_line.len());
+        let mut prev = None;
+        for c in single_line.chars() {
+            if c == '|' && prev != Some('\\') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            prev = Some(c);
+        }
+        escaped
+    }
+
+    fn render_cell(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        let old_in_table = ctx.in_table;
+        let old_inline_status = ctx.in_inline;
+        let old_list_depth = ctx.list_depth;
+
+        ctx.in_table = true;
+        ctx.in_inline = true;
+        ctx.list_depth = 0;
+        let content = render_children(url, dom, id, ctx);
+        ctx.in_table = old_in_table;
+        ctx.in_inline = old_inline_status;
+        ctx.list_depth = old_list_depth;
+
+        Ok(Self::format_cell_content(&content?))
+    }
+
+    /// Builds the cell grid, expanding colspan/rowspan into padding cells
+    fn build_grid(
+        &self,
+        url: &str,
+        dom: &Dom,
+        rows: &[NodeId],
+        ctx: &mut Context,
+    ) -> Result<Vec<Vec<Option<Cell>>>, ConvertError> {
+        let mut grid: Vec<Vec<Option<Cell>>> = Vec::with_capacity(rows.len());
+
+        for (row_index, &row_id) in rows.iter().enumerate() {
+            if grid.len() <= row_index {
+                grid.push(Vec::new());
+            }
+
+            let mut column = 0;
+            for cell_id in Self::collect_cells(dom, row_id) {
+                let (_, attrs) = dom.get_element_data(cell_id)?;
+
+                // skip columns occupied by rowspans from previous rows
+                while grid[row_index].get(column).is_some_and(Option::is_some) {
+                    column += 1;
+                }
+
+                let colspan = Self::span(attrs, "colspan");
+                let rowspan = Self::span(attrs, "rowspan").min(rows.len() - row_index);
+                let cell = Cell {
+                    content: self.render_cell(url, dom, cell_id, ctx)?,
+                    alignment: Alignment::from_attrs(attrs),
+                };
+
+                for r in row_index..row_index + rowspan {
+                    if grid.len() <= r {
+                        grid.push(Vec::new());
+                    }
+                    let row = &mut grid[r];
+                    if row.len() < column + colspan {
+                        row.resize(column + colspan, None);
+                    }
+                    for (offset, slot) in row[column..column + colspan].iter_mut().enumerate() {
+                        *slot = Some(if r == row_index && offset == 0 {
+                            cell.clone()
+                        } else {
+                            Cell::padding()
+                        });
+                    }
+                }
+                column += colspan;
+            }
+        }
+
+        Ok(grid)
+    }
+
+    fn format_row(cells: &[Cell]) -> String {
+        let contents: Vec<&str> = cells.iter().map(|cell| cell.content.as_str()).collect();
+        format!("| {} |", contents.join(" | "))
+    }
+
+    fn render_table(
+        &self,
+        url: &str,
+        dom: &Dom,
+        id: NodeId,
+        ctx: &mut Context,
+    ) -> Result<String, ConvertError> {
+        if Self::collect_rows(dom, id)
+            .iter()
+            .flat_map(|&row_id| Self::collect_cells(dom, row_id))
+            .any(|cell_id| Self::has_block_content(dom, cell_id))
+        {
+            return Ok(self.wrap_block(&dom.outer_html(id), ctx));
+        }
+
+        let rows = Self::collect_rows(dom, id);
+        let grid = self.build_grid(url, dom, &rows, ctx)?;
+        let column_count = grid.iter().map(Vec::len).max().unwrap_or(0);
+        if column_count == 0 {
+            return Ok(String::new());
+        }
+
+        let grid: Vec<Vec<Cell>> = grid
+            .into_iter()
+            .map(|mut row| {
+                row.resize(column_count, None);
+                row.into_iter()
+                    .map(|cell| cell.unwrap_or_else(Cell::padding))
+                    .collect()
+            })
+            .collect();
+
+        // the first row is always used as the header, GFM tables require one
+        let alignments: Vec<Alignment> = (0..column_count)
+            .map(|column| {
+                grid.iter()
+                    .map(|row| row[column].alignment)
+                    .find(|&alignment| alignment != Alignment::None)
+                    .unwrap_or(Alignment::None)
+            })
+            .collect();
+
+        let mut lines = Vec::with_capacity(grid.len() + 1);
+        lines.push(Self::format_row(&grid[0]));
+        lines.push(format!(
+            "| {} |",
+            alignments
+                .iter()
+                .map(|alignment| alignment.delimiter())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+        lines.extend(grid[1..].iter().map(|row| Self::format_row(row)));
+
+        // <caption> has no GFM equivalent, keep it as a line above the table
+        let caption = match dom.find_element_by_tag(id, "caption") {
+            Some(caption_id) => self.render_cell(url, dom, caption_id, ctx)?,
+            None => String::new(),
+        };
+        if !caption.is_empty() {
+            lines.insert(0, String::new());
+            lines.insert(0, caption);
+        }
+
+        Ok(self.wrap_block(&lines.join("\n"), ctx))
+    }
+
+    /// Adds list indentation and block separation around the table
+    fn wrap_block(&self, content: &str, ctx: &mut Context) -> String {
+        let indent = " ".repeat(ctx.list_depth);
+        let body = content
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{indent}{line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if ctx.list_depth > 0 {
+            format!("\n\n{body}")
+        } else {
+            format!("{body}\n\n")
+        }
+    }
+}
+
 impl Renderer for Table {
     fn matches(&self, dom: &Dom, id: NodeId) -> bool {
         let Some(node) = dom.node(id) else {
@@ -15,7 +342,7 @@ impl Renderer for Table {
         if let NodeData::Element { tag, .. } = &node.data {
             matches!(
                 tag.local.as_ref(),
-                "table" | "thead" | "tbody" | "tr" | "th" | "td"
+                "table" | "thead" | "tbody" | "tfoot" | "tr" | "th" | "td"
             )
         } else {
             false
@@ -29,19 +356,250 @@ impl Renderer for Table {
         id: NodeId,
         ctx: &mut Context,
     ) -> Result<String, ConvertError> {
-        ctx.in_table = true;
-        let content = render_children(url, dom, id, ctx)?;
-        ctx.in_table = false;
-
         let (tag, _) = dom.get_element_data(id)?;
 
         match tag.local.as_ref() {
-            "table" => Ok(format!("{content}\n\n")),
-            "tr" => Ok(format!("| {content} |\n")),
-            "th" | "td" => Ok(format!("{} | ", content.trim())),
-            _ => Ok(content),
+            "table" => self.render_table(url, dom, id, ctx),
+            "th" | "td" => self.render_cell(url, dom, id, ctx),
+            // table sections are only reached when rendering starts inside a table
+            _ => render_children(url, dom, id, ctx),
         }
     }
 }
 
 pub static TABLE: Table = Table;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::renderers;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// basic table tests
+    #[rstest]
+    #[case(
+        "<table><tr><th>Name</th><th>Age</th></tr><tr><td>Alice</td><td>30</td></tr></table>",
+        indoc! {r#"
+            | Name | Age |
+            | --- | --- |
+            | Alice | 30 |
+
+            "#}
+    )]
+    #[case(
+        "<table><thead><tr><th>A</th><th>B</th></tr></thead><tbody><tr><td>1</td><td>2</td></tr><tr><td>3</td><td>4</td></tr></tbody></table>",
+        indoc! {r#"
+            | A | B |
+            | --- | --- |
+            | 1 | 2 |
+            | 3 | 4 |
+
+            "#}
+    )]
+    #[case(
+        "<table><tr><td>no header</td><td>row</td></tr></table>",
+        indoc! {r#"
+            | no header | row |
+            | --- | --- |
+
+            "#}
+    )]
+    #[case(
+        "<table><caption>Scores</caption><tr><th>Team</th></tr><tr><td>Red</td></tr></table>",
+        indoc! {r#"
+            Scores
+
+            | Team |
+            | --- |
+            | Red |
+
+            "#}
+    )]
+    #[case("<table></table>", "")]
+    #[case("<table><tr></tr></table>", "")]
+    fn test_basic_tables(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render table");
+        assert_eq!(result, expected);
+    }
+
+    /// alignment tests
+    #[rstest]
+    #[case(
+        r#"<table><tr><th align="left">L</th><th align="center">C</th><th align="right">R</th><th>N</th></tr><tr><td>1</td><td>2</td><td>3</td><td>4</td></tr></table>"#,
+        indoc! {r#"
+            | L | C | R | N |
+            | :--- | :---: | ---: | --- |
+            | 1 | 2 | 3 | 4 |
+
+            "#}
+    )]
+    #[case(
+        r#"<table><tr><th style="text-align: center;">C</th><th style="color: red; TEXT-ALIGN:right">R</th></tr><tr><td>1</td><td>2</td></tr></table>"#,
+        indoc! {r#"
+            | C | R |
+            | :---: | ---: |
+            | 1 | 2 |
+
+            "#}
+    )]
+    #[case(
+        r#"<table><tr><th>A</th></tr><tr><td style="text-align:right">1</td></tr></table>"#,
+        indoc! {r#"
+            | A |
+            | ---: |
+            | 1 |
+
+            "#}
+    )]
+    fn test_table_alignment(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render table");
+        assert_eq!(result, expected);
+    }
+
+    /// colspan and rowspan tests
+    #[rstest]
+    #[case(
+        r#"<table><tr><th colspan="2">Wide</th><th>C</th></tr><tr><td>1</td><td>2</td><td>3</td></tr></table>"#,
+        indoc! {r#"
+            | Wide |  | C |
+            | --- | --- | --- |
+            | 1 | 2 | 3 |
+
+            "#}
+    )]
+    #[case(
+        r#"<table><tr><th>A</th><th>B</th></tr><tr><td rowspan="2">tall</td><td>1</td></tr><tr><td>2</td></tr></table>"#,
+        indoc! {r#"
+            | A | B |
+            | --- | --- |
+            | tall | 1 |
+            |  | 2 |
+
+            "#}
+    )]
+    #[case(
+        r#"<table><tr><th>A</th><th>B</th><th>C</th></tr><tr><td>1</td></tr></table>"#,
+        indoc! {r#"
+            | A | B | C |
+            | --- | --- | --- |
+            | 1 |  |  |
+
+            "#}
+    )]
+    #[case(
+        r#"<table><tr><th>A</th><th>B</th></tr><tr><td rowspan="5" colspan="0">1</td><td>2</td></tr></table>"#,
+        indoc! {r#"
+            | A | B |
+            | --- | --- |
+            | 1 | 2 |
+
+            "#}
+    )]
+    fn test_table_spans(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render table");
+        assert_eq!(result, expected);
+    }
+
+    /// cell content tests
+    #[rstest]
+    #[case(
+        "<table><tr><th>Expr</th></tr><tr><td>a | b</td></tr></table>",
+        indoc! {r#"
+            | Expr |
+            | --- |
+            | a \| b |
+
+            "#}
+    )]
+    #[case(
+        "<table><tr><th>Code</th></tr><tr><td><code>x || y</code></td></tr></table>",
+        indoc! {r#"
+            | Code |
+            | --- |
+            | `x \|\| y` |
+
+            "#}
+    )]
+    #[case(
+        r#"<table><tr><th><strong>Bold</strong> head</th></tr><tr><td><a href="https://example.com">link</a></td></tr></table>"#,
+        indoc! {r#"
+            | **Bold** head |
+            | --- |
+            | [link](https://example.com) |
+
+            "#}
+    )]
+    #[case(
+        "<table><tr><th>Text</th></tr><tr><td><p>first</p><p>second</p></td></tr></table>",
+        indoc! {r#"
+            | Text |
+            | --- |
+            | first<br>second |
+
+            "#}
+    )]
+    fn test_table_cell_content(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render table");
+        assert_eq!(result, expected);
+    }
+
+    /// block content falls back to raw HTML
+    #[rstest]
+    #[case(
+        "<table><tr><th>List</th></tr><tr><td><ul><li>a</li><li>b</li></ul></td></tr></table>",
+        "<table><tbody><tr><th>List</th></tr><tr><td><ul><li>a</li><li>b</li></ul></td></tr></tbody></table>\n\n"
+    )]
+    #[case(
+        r#"<table class="code"><tr><td><pre>let x = 1;
+let y = 2;</pre></td></tr></table>"#,
+        "<table class=\"code\"><tbody><tr><td><pre>let x = 1;\nlet y = 2;</pre></td></tr></tbody></table>\n\n"
+    )]
+    // blank lines in <pre> would end the HTML block
+    #[case(
+        "<table><tr><td><pre>a\n\nb\n  \n\n\nc\n</pre></td></tr></table>",
+        "<table><tbody><tr><td><pre>a&#10;\nb&#10;  &#10;&#10;\nc\n</pre></td></tr></tbody></table>\n\n"
+    )]
+    fn test_table_html_fallback(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render table");
+        assert_eq!(result, expected);
+    }
+
+    /// tables in lists
+    #[rstest]
+    #[case(
+        "<ul><li>Item<table><tr><th>A</th></tr><tr><td>1</td></tr></table></li></ul>",
+        indoc! {r#"
+            - Item
+
+              | A |
+              | --- |
+              | 1 |
+
+            "#}
+    )]
+    fn test_table_in_list(#[case] html: &str, #[case] expected: &str) {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let mut context = Context::default();
+        let result = renderers::render_node("", &dom, dom.document, &mut context)
+            .expect("Failed to render table");
+        assert_eq!(result, expected);
+    }
+}
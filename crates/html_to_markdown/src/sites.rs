@@ -0,0 +1,225 @@
+//! Site-specific extraction for pages whose layout the generic content detection gets wrong.
+//!
+//! A [`SiteExtractor`] is chosen by the host of the page URL. It picks the content root,
+//! names the site chrome left out of the output and contributes front-matter values.
+
+use crate::{
+    dom::{Dom, NodeId},
+    frontmatters::title::extract_meta_content,
+    utils::url,
+};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+pub mod github;
+pub mod hatena;
+pub mod note;
+pub mod qiita;
+pub mod zenn;
+
+pub trait SiteExtractor: Sync + Send {
+    /// Name of the site, for debugging
+    fn name(&self) -> &'static str;
+
+    /// Host patterns of the site, `*.example.com` matches the subdomains of `example.com`
+    fn hosts(&self) -> &'static [&'static str];
+
+    /// Root element of the article, none falls back to the generic content detection
+    fn content_root(&self, url: &str, dom: &Dom) -> Option<NodeId>;
+
+    /// CSS selector of site chrome (like counts, author cards, tag bars) left out of the output
+    fn chrome_selector(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Front-matter value for `key`, none falls back to the generic extractors
+    fn frontmatter(&self, _key: &str, _url: &str, _dom: &Dom) -> Option<String> {
+        None
+    }
+}
+
+impl std::fmt::Debug for dyn SiteExtractor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SiteExtractor").field(&self.name()).finish()
+    }
+}
+
+static SITE_EXTRACTORS: LazyLock<Vec<&'static dyn SiteExtractor>> = LazyLock::new(|| {
+    vec![
+        &qiita::EXTRACTOR as &'static dyn SiteExtractor,
+        &zenn::EXTRACTOR as &'static dyn SiteExtractor,
+        &note::EXTRACTOR as &'static dyn SiteExtractor,
+        &hatena::EXTRACTOR as &'static dyn SiteExtractor,
+        &github::EXTRACTOR as &'static dyn SiteExtractor,
+    ]
+});
+
+/// Extractor for the host of `url`, the `custom` extractors take priority over the built-in ones
+pub fn find_site_extractor(
+    url: &str,
+    custom: &[&'static dyn SiteExtractor],
+) -> Option<&'static dyn SiteExtractor> {
+    let host = url::host(url)?;
+    custom
+        .iter()
+        .chain(SITE_EXTRACTORS.iter())
+        .find(|extractor| {
+            extractor
+                .hosts()
+                .iter()
+                .any(|pattern| url::host_matches(pattern, &host))
+        })
+        .copied()
+}
+
+/// Detaches the site chrome from the tree
+pub fn strip_chrome(dom: &mut Dom, site: &dyn SiteExtractor) {
+    let Some(chrome) = site
+        .chrome_selector()
+        .and_then(|selector| dom.select(dom.document, selector).ok())
+    else {
+        return;
+    };
+    for id in chrome {
+        dom.detach(id);
+    }
+}
+
+/// First element of the document matching a selector of an extractor
+pub(crate) fn select_first(dom: &Dom, selector: &str) -> Option<NodeId> {
+    dom.select_first(dom.document, selector).ok().flatten()
+}
+
+/// `content` of `<meta property="...">`, e.g. `og:title` which has no site name suffix on most sites
+pub(crate) fn meta_property(dom: &Dom, property: &str) -> Option<String> {
+    extract_meta_content(dom, "property", property)
+}
+
+/// Normalized text of the first element matching the selector
+pub(crate) fn selector_text(dom: &Dom, selector: &str) -> Option<String> {
+    let id = select_first(dom, selector)?;
+    let text = dom
+        .collect_text_content(id)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// First path segment of URLs like `https://example.com/{user}/{kind}/...`
+pub(crate) fn path_user<'a>(url: &'a str, kind: &str) -> Option<&'a str> {
+    match url::path_segments(url).as_slice() {
+        [user, segment, ..] if *segment == kind => Some(*user),
+        _ => None,
+    }
+}
+
+/// Texts of the elements matching the selector as a YAML flow sequence (`[rust, wasm]`).
+/// Tags keep no leading `#` and spaces become `-`, as in Obsidian tags.
+pub(crate) fn selector_tags(dom: &Dom, selector: &str) -> Option<String> {
+    let mut seen = HashSet::new();
+    let tags: Vec<String> = dom
+        .select(dom.document, selector)
+        .ok()?
+        .into_iter()
+        .map(|id| {
+            dom.collect_text_content(id)
+                .trim()
+                .trim_start_matches('#')
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.clone()))
+        .collect();
+
+    (!tags.is_empty()).then(|| format!("[{}]", tags.join(", ")))
+}
+
+/// Publication date from `<meta property="article:published_time">`
+pub(crate) fn published_time(dom: &Dom) -> Option<String> {
+    meta_property(dom, "article:published_time")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dom::selector::Selector, parser};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("https://qiita.com/user/items/abc", Some("Qiita"))]
+    #[case("https://zenn.dev/user/articles/abc", Some("Zenn"))]
+    #[case("https://note.com/user/n/n123", Some("note"))]
+    #[case(
+        "https://user.hatenablog.com/entry/2024/01/01/000000",
+        Some("Hatena Blog")
+    )]
+    #[case("https://USER.HATEBLO.JP/entry/1", Some("Hatena Blog"))]
+    #[case("https://github.com/owner/repo", Some("GitHub"))]
+    #[case("https://example.com/qiita.com", None)]
+    #[case("", None)]
+    fn test_find_site_extractor(#[case] url: &str, #[case] expected: Option<&str>) {
+        let name = find_site_extractor(url, &[]).map(|site| site.name());
+        assert_eq!(name, expected);
+    }
+
+    struct Custom;
+
+    impl SiteExtractor for Custom {
+        fn name(&self) -> &'static str {
+            "Custom"
+        }
+
+        fn hosts(&self) -> &'static [&'static str] {
+            &["qiita.com"]
+        }
+
+        fn content_root(&self, _url: &str, dom: &Dom) -> Option<NodeId> {
+            dom.find_body()
+        }
+    }
+
+    static CUSTOM: Custom = Custom;
+
+    #[test]
+    fn test_custom_extractor_priority() {
+        let site = find_site_extractor("https://qiita.com/user/items/abc", &[&CUSTOM]);
+        assert_eq!(site.map(|site| site.name()), Some("Custom"));
+    }
+
+    /// selectors of the built-in extractors are valid
+    #[test]
+    fn test_chrome_selectors() {
+        for site in SITE_EXTRACTORS.iter() {
+            if let Some(selector) = site.chrome_selector() {
+                assert!(
+                    Selector::parse(selector).is_ok(),
+                    "{}: {selector}",
+                    site.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_strip_chrome() {
+        let html = r#"<div class="post"><p>Text</p><div class="LikeButton_root__x1y2">12</div><span class="likes">3</span></div>"#;
+        let mut dom = parser::parse_html(html).expect("Failed to parse HTML");
+        strip_chrome(&mut dom, &zenn::EXTRACTOR);
+        let post = select_first(&dom, ".post").expect("No post");
+        assert_eq!(dom.collect_text_content(post), "Text3");
+    }
+
+    #[test]
+    fn test_selector_tags() {
+        let html = r##"<a href="/tags/rust">#Rust</a><a href="/tags/web-assembly">Web Assembly</a><a href="/tags/rust">Rust</a><a href="/users/a">User</a>"##;
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        assert_eq!(
+            selector_tags(&dom, "a[href*='/tags/']").as_deref(),
+            Some("[Rust, Web-Assembly]")
+        );
+        assert_eq!(selector_tags(&dom, "a[href*='/topics/']"), None);
+    }
+}
@@ -0,0 +1,90 @@
+use super::{SiteExtractor, select_first, selector_tags, selector_text};
+use crate::{
+    dom::{Dom, NodeId},
+    utils::url,
+};
+
+/// GitHub repository READMEs and issue or pull request descriptions
+pub struct GitHub;
+
+impl GitHub {
+    /// `https://github.com/{owner}/{repo}/issues/{number}` and pull requests
+    fn is_issue(url: &str) -> bool {
+        matches!(
+            url::path_segments(url).get(2),
+            Some(&("issues" | "pull" | "discussions"))
+        )
+    }
+}
+
+impl SiteExtractor for GitHub {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &["github.com"]
+    }
+
+    fn content_root(&self, url: &str, dom: &Dom) -> Option<NodeId> {
+        if Self::is_issue(url)
+            && let Some(body) = select_first(dom, ".comment-body")
+        {
+            return Some(body);
+        }
+        select_first(dom, ".markdown-body")
+    }
+
+    fn chrome_selector(&self) -> Option<&'static str> {
+        Some(
+            ".zeroclipboard-container, .comment-reactions, .timeline-comment-header, \
+             .js-comment-edit-history",
+        )
+    }
+
+    fn frontmatter(&self, key: &str, url: &str, dom: &Dom) -> Option<String> {
+        match key {
+            // og:title has an " · Issue #1 · owner/repo" suffix
+            "title" if Self::is_issue(url) => selector_text(dom, ".js-issue-title"),
+            "author" if Self::is_issue(url) => {
+                selector_text(dom, ".timeline-comment-header .author")
+            }
+            "author" => url::path_segments(url)
+                .first()
+                .map(|owner| owner.to_string()),
+            "tags" => selector_tags(dom, "a.topic-tag"),
+            _ => None,
+        }
+    }
+}
+
+pub static EXTRACTOR: GitHub = GitHub;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r#"<html><head><meta property="og:title" content="Panic on empty input · Issue #42 · owner/repo"></head>
+<body><div class="BorderGrid"><a class="topic-tag" href="/topics/rust">rust</a><a class="topic-tag" href="/topics/cli">cli</a></div>
+<h1><bdi class="js-issue-title markdown-title">Panic on empty input</bdi></h1>
+<div class="timeline-comment-header"><a class="author" href="/erin">erin</a></div>
+<div class="comment-body markdown-body"><p>Steps</p></div></body></html>"#;
+
+    #[rstest]
+    #[case(
+        "https://github.com/owner/repo/issues/42",
+        "title",
+        Some("Panic on empty input")
+    )]
+    #[case("https://github.com/owner/repo", "title", None)]
+    #[case("https://github.com/owner/repo/issues/42", "author", Some("erin"))]
+    #[case("https://github.com/owner/repo", "author", Some("owner"))]
+    #[case("https://github.com/owner/repo", "tags", Some("[rust, cli]"))]
+    fn test_frontmatter(#[case] url: &str, #[case] key: &str, #[case] expected: Option<&str>) {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        assert_eq!(EXTRACTOR.frontmatter(key, url, &dom).as_deref(), expected);
+    }
+}
@@ -0,0 +1,83 @@
+use super::{SiteExtractor, select_first, selector_tags, selector_text};
+use crate::dom::{Dom, NodeId};
+
+/// Hatena Blog entries on the blog domains of Hatena
+pub struct Hatena;
+
+impl Hatena {
+    fn attribute(dom: &Dom, selector: &str, name: &str) -> Option<String> {
+        let id = select_first(dom, selector)?;
+        let (_, attrs) = dom.get_element_data(id).ok()?;
+        attrs.get(name).cloned()
+    }
+}
+
+impl SiteExtractor for Hatena {
+    fn name(&self) -> &'static str {
+        "Hatena Blog"
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &[
+            "*.hatenablog.com",
+            "*.hatenablog.jp",
+            "*.hateblo.jp",
+            "*.hatenadiary.com",
+            "*.hatenadiary.jp",
+        ]
+    }
+
+    fn content_root(&self, _url: &str, dom: &Dom) -> Option<NodeId> {
+        select_first(dom, ".entry-content")
+    }
+
+    fn chrome_selector(&self) -> Option<&'static str> {
+        Some(
+            ".hatena-star-container, .social-buttons, .entry-footer-modules, .customized-footer, \
+             .google-afc-user-container, .hatena-module",
+        )
+    }
+
+    fn frontmatter(&self, key: &str, _url: &str, dom: &Dom) -> Option<String> {
+        match key {
+            // og:title and <title> have a " - {blog name}" suffix
+            "title" => selector_text(dom, ".entry-title"),
+            // <html data-author="..."> is the Hatena ID of the blog owner
+            "author" => Self::attribute(dom, "html[data-author]", "data-author"),
+            "tags" => selector_tags(dom, "a[href*='/archive/category/']"),
+            "published" => Self::attribute(dom, ".entry-date time[datetime]", "datetime"),
+            _ => None,
+        }
+    }
+}
+
+pub static EXTRACTOR: Hatena = Hatena;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r#"<html data-author="dave"><head><meta property="og:title" content="Vimの設定 - daveの日記"></head>
+<body><article class="entry"><header><div class="date entry-date"><a href="/archive/2024/03/01"><time datetime="2024-03-01T12:00:00Z">2024-03-01</time></a></div>
+<h1 class="entry-title"><a href="/entry/2024/03/01/120000">Vimの設定</a></h1>
+<div class="entry-categories categories"><a href="/archive/category/Vim" class="entry-category-link">Vim</a></div></header>
+<div class="entry-content hatenablog-entry"><p>本文</p></div></article></body></html>"#;
+
+    #[rstest]
+    #[case("title", Some("Vimの設定"))]
+    #[case("author", Some("dave"))]
+    #[case("tags", Some("[Vim]"))]
+    #[case("published", Some("2024-03-01T12:00:00Z"))]
+    fn test_frontmatter(#[case] key: &str, #[case] expected: Option<&str>) {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        let value = EXTRACTOR.frontmatter(
+            key,
+            "https://dave.hatenablog.com/entry/2024/03/01/120000",
+            &dom,
+        );
+        assert_eq!(value.as_deref(), expected);
+    }
+}
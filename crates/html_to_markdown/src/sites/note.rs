@@ -0,0 +1,61 @@
+use super::{SiteExtractor, meta_property, path_user, published_time, select_first, selector_tags};
+use crate::dom::{Dom, NodeId};
+
+/// note.com articles (`https://note.com/{user}/n/{id}`)
+pub struct Note;
+
+impl SiteExtractor for Note {
+    fn name(&self) -> &'static str {
+        "note"
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &["note.com"]
+    }
+
+    fn content_root(&self, _url: &str, dom: &Dom) -> Option<NodeId> {
+        select_first(dom, ".note-common-styles__textnote-body")
+    }
+
+    fn chrome_selector(&self) -> Option<&'static str> {
+        Some(
+            ".o-noteLikeV3, .o-noteContentHeader__info, .m-shareButtons, .o-creatorProfile, \
+             .m-tagList, .o-noteFooter",
+        )
+    }
+
+    fn frontmatter(&self, key: &str, url: &str, dom: &Dom) -> Option<String> {
+        match key {
+            // <title> has a "｜{creator}｜note" suffix
+            "title" => meta_property(dom, "og:title"),
+            "author" => path_user(url, "n").map(str::to_string),
+            "tags" => selector_tags(dom, "a[href*='/hashtag/']"),
+            "published" => published_time(dom),
+            _ => None,
+        }
+    }
+}
+
+pub static EXTRACTOR: Note = Note;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r##"<html><head><meta property="og:title" content="毎日書く習慣"></head>
+<body><div class="m-tagList"><a href="/hashtag/習慣">#習慣</a><a href="/hashtag/エッセイ">#エッセイ</a></div>
+<div class="note-common-styles__textnote-body"><p>本文</p></div></body></html>"##;
+
+    #[rstest]
+    #[case("title", Some("毎日書く習慣"))]
+    #[case("author", Some("carol"))]
+    #[case("tags", Some("[習慣, エッセイ]"))]
+    fn test_frontmatter(#[case] key: &str, #[case] expected: Option<&str>) {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        let value = EXTRACTOR.frontmatter(key, "https://note.com/carol/n/n0123456789ab", &dom);
+        assert_eq!(value.as_deref(), expected);
+    }
+}
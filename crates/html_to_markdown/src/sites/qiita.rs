@@ -0,0 +1,63 @@
+use super::{SiteExtractor, meta_property, path_user, published_time, select_first, selector_tags};
+use crate::dom::{Dom, NodeId};
+
+/// Qiita articles (`https://qiita.com/{user}/items/{id}`) and Qiita Team
+pub struct Qiita;
+
+impl SiteExtractor for Qiita {
+    fn name(&self) -> &'static str {
+        "Qiita"
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &["qiita.com", "*.qiita.com"]
+    }
+
+    fn content_root(&self, _url: &str, dom: &Dom) -> Option<NodeId> {
+        select_first(dom, "#personal-public-article-body")
+            .or_else(|| select_first(dom, ".it-MdContent"))
+    }
+
+    fn chrome_selector(&self) -> Option<&'static str> {
+        Some(".it-Tags, .it-Likes, .it-Actions, .it-AuthorCard, .it-Toc, .it-ArticleFooter")
+    }
+
+    fn frontmatter(&self, key: &str, url: &str, dom: &Dom) -> Option<String> {
+        match key {
+            // <title> has a " - Qiita" suffix
+            "title" => meta_property(dom, "og:title"),
+            "author" => path_user(url, "items").map(str::to_string),
+            "tags" => selector_tags(dom, "a[href*='/tags/']"),
+            "published" => published_time(dom),
+            _ => None,
+        }
+    }
+}
+
+pub static EXTRACTOR: Qiita = Qiita;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r#"<html><head><title>Ownership in Rust - Qiita</title>
+<meta property="og:title" content="Ownership in Rust">
+<meta property="article:published_time" content="2024-05-01T09:00:00+09:00"></head>
+<body><div class="it-Tags"><a href="/tags/rust">Rust</a><a href="/tags/beginner">初心者</a></div>
+<section class="it-MdContent"><div id="personal-public-article-body"><p>Body</p></div></section></body></html>"#;
+
+    #[rstest]
+    #[case("title", Some("Ownership in Rust"))]
+    #[case("author", Some("alice"))]
+    #[case("tags", Some("[Rust, 初心者]"))]
+    #[case("published", Some("2024-05-01T09:00:00+09:00"))]
+    #[case("source", None)]
+    fn test_frontmatter(#[case] key: &str, #[case] expected: Option<&str>) {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        let value = EXTRACTOR.frontmatter(key, "https://qiita.com/alice/items/0123abcd", &dom);
+        assert_eq!(value.as_deref(), expected);
+    }
+}
@@ -0,0 +1,80 @@
+use super::{SiteExtractor, meta_property, path_user, published_time, select_first, selector_tags};
+use crate::dom::{Dom, NodeId};
+
+/// Zenn articles (`https://zenn.dev/{user}/articles/{slug}`) and book chapters
+pub struct Zenn;
+
+impl SiteExtractor for Zenn {
+    fn name(&self) -> &'static str {
+        "Zenn"
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &["zenn.dev"]
+    }
+
+    fn content_root(&self, _url: &str, dom: &Dom) -> Option<NodeId> {
+        // "znc" is the class of rendered Zenn Markdown
+        select_first(dom, ".znc")
+    }
+
+    fn chrome_selector(&self) -> Option<&'static str> {
+        // CSS module classes like "LikeButton_root__x1y2"
+        Some(
+            "[class*=LikeButton_], [class*=ShareButtons_], [class*=ArticleSidebar_], \
+             [class*=AuthorCard_], [class*=TopicList_], [class*=ArticleFooter_]",
+        )
+    }
+
+    fn frontmatter(&self, key: &str, url: &str, dom: &Dom) -> Option<String> {
+        match key {
+            // <title> has a "｜Zenn" suffix
+            "title" => meta_property(dom, "og:title"),
+            "author" => path_user(url, "articles")
+                .or_else(|| path_user(url, "books"))
+                .map(str::to_string),
+            "tags" => selector_tags(dom, "a[href*='/topics/']"),
+            "published" => published_time(dom),
+            _ => None,
+        }
+    }
+}
+
+pub static EXTRACTOR: Zenn = Zenn;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const HTML: &str = r#"<html><head><title>Tokioで非同期処理｜Zenn</title>
+<meta property="og:title" content="Tokioで非同期処理"></head>
+<body><div class="TopicList_container__a1b2"><a href="/topics/rust">Rust</a><a href="/topics/tokio">Tokio</a></div>
+<div class="znc"><p>本文</p></div></body></html>"#;
+
+    #[rstest]
+    #[case(
+        "https://zenn.dev/bob/articles/tokio-async",
+        "title",
+        Some("Tokioで非同期処理")
+    )]
+    #[case("https://zenn.dev/bob/articles/tokio-async", "author", Some("bob"))]
+    #[case(
+        "https://zenn.dev/bob/books/rust-book/viewer/intro",
+        "author",
+        Some("bob")
+    )]
+    #[case("https://zenn.dev/topics/rust", "author", None)]
+    #[case(
+        "https://zenn.dev/bob/articles/tokio-async",
+        "tags",
+        Some("[Rust, Tokio]")
+    )]
+    #[case("https://zenn.dev/bob/articles/tokio-async", "published", None)]
+    fn test_frontmatter(#[case] url: &str, #[case] key: &str, #[case] expected: Option<&str>) {
+        let dom = parser::parse_html(HTML).expect("Failed to parse HTML");
+        assert_eq!(EXTRACTOR.frontmatter(key, url, &dom).as_deref(), expected);
+    }
+}
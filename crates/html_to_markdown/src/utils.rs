@@ -1,4 +1,6 @@
+pub mod escape;
 pub mod filtering;
+pub mod url;
 
 use std::borrow::Cow;
 
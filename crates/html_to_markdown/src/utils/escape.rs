@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+
+/// Where a piece of text ends up in the Markdown output.
+/// Each context needs a slightly different set of characters escaped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EscapeContext {
+    /// Regular text. `line_start` is true when the text begins a new block,
+    /// so block markers (`#`, `>`, `-`, `1.`) must be escaped too.
+    Text { line_start: bool },
+    /// Text between `[` and `]` of a link
+    LinkText,
+    /// Text inside a GFM table cell
+    TableCell,
+    /// Text after the `#` marker of an ATX heading
+    Heading,
+    /// Code spans and code blocks are emitted verbatim
+    Code,
+}
+
+/// Characters that can change the meaning of Markdown (including Obsidian extensions)
+fn is_special(c: char) -> bool {
+    matches!(
+        c,
+        '\\' | '*'
+            | '_'
+            | '`'
+            | '['
+            | ']'
+            | '<'
+            | '>'
+            | '#'
+            | '~'
+            | '='
+            | '$'
+            | '%'
+            | '&'
+            | '-'
+            | '+'
+            | '.'
+            | ')'
+            | '|'
+    )
+}
+
+/// Checks for an HTML entity like `&amp;`, `&#123;` or `&#x1F600;` starting at `start`
+fn is_entity_reference(chars: &[char], start: usize) -> bool {
+    let rest = &chars[start + 1..];
+    let Some(end) = rest.iter().take(32).position(|&c| c == ';') else {
+        return false;
+    };
+    let name = &rest[..end];
+    !name.is_empty()
+        && name
+            .iter()
+            .enumerate()
+            .all(|(i, &c)| c.is_ascii_alphanumeric() || (i == 0 && c == '#'))
+}
+
+/// Length of a leading block marker (`#`, `>`, `-`, `+`, `*`, `1.`, `1)`) that needs escaping,
+/// returned as the index of the character to escape
+fn block_marker_index(chars: &[char]) -> Option<usize> {
+    let first = *chars.first()?;
+    let next_is_space = |i: usize| chars.get(i).is_none_or(|c| c.is_whitespace());
+
+    match first {
+        '#' | '>' => Some(0),
+        '-' | '+' | '*' if next_is_space(1) || chars.get(1) == Some(&first) => Some(0),
+        '0'..='9' => {
+            let digits = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+            // CommonMark ordered list markers have at most 9 digits
+            (digits <= 9
+                && matches!(chars.get(digits), Some('.' | ')'))
+                && next_is_space(digits + 1))
+            .then_some(digits)
+        }
+        _ => None,
+    }
+}
+
+/// Escapes Markdown syntax in a text node so it renders literally.
+///
+/// Rules are intentionally conservative to keep the note readable:
+/// `*` and `_` are left alone where they cannot form emphasis (`a * b`, `snake_case`),
+/// `<` only when it could start a tag, and Obsidian syntax (`==`, `~~`, `%%`, `$`, `#tag`)
+/// is escaped as well.
+pub fn escape_markdown(text: &str, context: EscapeContext) -> Cow<'_, str> {
+    if context == EscapeContext::Code || !text.chars().any(is_special) {
+        return Cow::Borrowed(text);
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut escaped = String::with_capacity(text.len() + 8);
+
+    let marker_index = match context {
+        EscapeContext::Text { line_start: true } => block_marker_index(&chars),
+        _ => None,
+    };
+
+    // "## Title #" would lose the trailing "#" as an optional closing sequence
+    let closing_sequence_start = if context == EscapeContext::Heading {
+        let trimmed_len = text.trim_end().chars().count();
+        let run = chars[..trimmed_len]
+            .iter()
+            .rev()
+            .take_while(|&&c| c == '#')
+            .count();
+        let start = trimmed_len - run;
+        (run > 0 && (start == 0 || chars[start - 1].is_whitespace())).then_some(start)
+    } else {
+        None
+    };
+
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+        let is_space = |c: Option<char>| c.is_some_and(char::is_whitespace);
+
+        let needs_escape = if Some(i) == marker_index || Some(i) == closing_sequence_start {
+            true
+        } else {
+            match c {
+                '\\' => next.is_none_or(|n| n.is_ascii_punctuation()),
+                '`' | '[' | ']' | '$' => true,
+                // "a * b" cannot open or close emphasis
+                '*' => !(is_space(prev) && is_space(next)),
+                // intraword underscores never form emphasis
+                '_' => {
+                    let alnum = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+                    !(alnum(prev) && alnum(next) || is_space(prev) && is_space(next))
+                }
+                '<' => {
+                    next.is_some_and(|n| n.is_ascii_alphabetic() || matches!(n, '/' | '!' | '?'))
+                }
+                // Obsidian strikethrough, highlight and comment markers
+                '~' | '=' | '%' => prev == Some(c) || next == Some(c),
+                '&' => is_entity_reference(&chars, i),
+                // the table renderer leaves already escaped pipes alone
+                '|' => context == EscapeContext::TableCell,
+                // Obsidian tags (#tag) start after whitespace
+                '#' => {
+                    (prev.is_none() || is_space(prev))
+                        && next.is_some_and(|n| !n.is_whitespace() && n != '#')
+                }
+                _ => false,
+            }
+        };
+
+        if needs_escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// regular text in the middle of a line
+    #[rstest]
+    #[case("plain text", "plain text")]
+    #[case("*not emphasis*", r"\*not emphasis\*")]
+    #[case("2 * 3 = 6", "2 * 3 = 6")]
+    #[case("_underscored_", r"\_underscored\_")]
+    #[case("snake_case_name", "snake_case_name")]
+    #[case("use `code` here", r"use \`code\` here")]
+    #[case("[not a link](url)", r"\[not a link\](url)")]
+    #[case("[[wikilink]]", r"\[\[wikilink\]\]")]
+    #[case("a <div> tag", r"a \<div> tag")]
+    #[case("1 < 2 and 3 > 2", "1 < 2 and 3 > 2")]
+    #[case("~~strike~~ and ~5", r"\~\~strike\~\~ and ~5")]
+    #[case("==highlight== a=b", r"\=\=highlight\=\= a=b")]
+    #[case("costs $10", r"costs \$10")]
+    #[case("%%comment%% 100%", r"\%\%comment\%\% 100%")]
+    #[case("&copy; & more", r"\&copy; & more")]
+    #[case("C# and #tag", r"C# and \#tag")]
+    #[case("# not at start", "# not at start")]
+    #[case(r"C:\Users\name", r"C:\Users\name")]
+    #[case(r"escaped \* star", r"escaped \\\* star")]
+    #[case("- not at start", "- not at start")]
+    fn test_escape_inline_text(#[case] input: &str, #[case] expected: &str) {
+        let context = EscapeContext::Text { line_start: false };
+        assert_eq!(escape_markdown(input, context), expected);
+    }
+
+    /// text at the start of a block
+    #[rstest]
+    #[case("# Not a heading", r"\# Not a heading")]
+    #[case("> not a quote", r"\> not a quote")]
+    #[case("- not a list", r"\- not a list")]
+    #[case("+ not a list", r"\+ not a list")]
+    #[case("* not a list", r"\* not a list")]
+    #[case("--- not a rule", r"\--- not a rule")]
+    #[case("1. not a list", r"1\. not a list")]
+    #[case("2024) not a list", r"2024\) not a list")]
+    #[case("2024.10 version", "2024.10 version")]
+    #[case("-1 degrees", "-1 degrees")]
+    #[case("plain", "plain")]
+    fn test_escape_line_start(#[case] input: &str, #[case] expected: &str) {
+        let context = EscapeContext::Text { line_start: true };
+        assert_eq!(escape_markdown(input, context), expected);
+    }
+
+    /// context specific rules
+    #[rstest]
+    #[case("# text", EscapeContext::LinkText, "# text")]
+    #[case("[brackets]", EscapeContext::LinkText, r"\[brackets\]")]
+    #[case("1. item", EscapeContext::TableCell, "1. item")]
+    #[case("a | b", EscapeContext::TableCell, r"a \| b")]
+    #[case(r"a \| b", EscapeContext::TableCell, r"a \\\| b")]
+    #[case("a | b", EscapeContext::LinkText, "a | b")]
+    #[case("Title #", EscapeContext::Heading, r"Title \#")]
+    #[case("Title ##  ", EscapeContext::Heading, r"Title \##  ")]
+    #[case("C#", EscapeContext::Heading, "C#")]
+    #[case("*emphasis* #tag", EscapeContext::Heading, r"\*emphasis\* \#tag")]
+    #[case("*raw* `code` [x]", EscapeContext::Code, "*raw* `code` [x]")]
+    fn test_escape_contexts(
+        #[case] input: &str,
+        #[case] context: EscapeContext,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(escape_markdown(input, context), expected);
+    }
+
+    #[test]
+    fn test_escape_markdown_cow_efficiency() {
+        let context = EscapeContext::Text { line_start: true };
+        assert!(matches!(
+            escape_markdown("nothing special", context),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(escape_markdown("a*b", context), Cow::Owned(_)));
+    }
+}
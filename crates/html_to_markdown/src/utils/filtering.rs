@@ -1,77 +1,320 @@
-use std::{collections::HashSet, sync::LazyLock};
-
-static IGNORE_KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
-    [
-        "sidebar",
-        "author",
-        "publication",
-        "mobile",
-        "share",
-        "userinfo",      // author information
-        "topics",        // assume tag or topic section
-        "comment",       // comments section
-        "navigation",    // navigation elements
-        "footer",        // footer elements
-        "advertisement", // ads or promotional content
-        "social",
-    ]
-    .into_iter()
-    .collect()
-});
-
-pub fn should_ignore_class(class_value: &str) -> bool {
-    if class_value.is_empty() {
-        return false;
-    }
-
-    let lowercase_class = class_value.to_ascii_lowercase();
-    IGNORE_KEYWORDS
-        .iter()
-        .any(|&keyword| lowercase_class.contains(keyword))
+use crate::{
+    dom::{Dom, NodeId, selector::Selector},
+    error::ConvertError,
+    utils::url,
+};
+use std::collections::HashSet;
+
+/// Container elements checked against the deny tokens
+const TOKEN_TAGS: [&str; 2] = ["div", "aside"];
+
+/// Class names removed by the default rules
+const DEFAULT_DENY_TOKENS: [&str; 12] = [
+    "sidebar",
+    "author",
+    "publication",
+    "mobile",
+    "share",
+    "userinfo",      // author information
+    "topics",        // assume tag or topic section
+    "comment",       // comments section
+    "navigation",    // navigation elements
+    "footer",        // footer elements
+    "advertisement", // ads or promotional content
+    "social",
+];
+
+/// Rules for elements left out of the output, like sidebars, share buttons and comments.
+///
+/// A `div` or `aside` is removed when one of its class names is a deny token, and any
+/// element is removed when it matches a deny selector, unless an allow token or an allow selector
+/// matches it. Tokens are compared case-insensitively with whole class names, so `sidebar` removes
+/// `class="left sidebar"` but keeps `class="sidebar-toggle"`; a selector like
+/// `[class*=sidebar]` matches parts of class names, and `span.author` widens the rules to other
+/// elements. The root of the rendered content is never removed.
+///
+/// The default rules remove common page chrome (`sidebar`, `author`, `share`, `comment`, ...).
+///
+/// # Example
+///
+/// ```rust
+/// use html_to_markdown::{ConvertOptions, RemovalRules};
+///
+/// let rules = RemovalRules::default()
+///     .allow_token("author")
+///     .deny_selector(".ad, [class*=promo]")?
+///     .host("*.example.com", RemovalRules::empty().deny_token("related"));
+/// let options = ConvertOptions::default().removal_rules(rules);
+/// # Ok::<(), html_to_markdown::error::ConvertError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemovalRules {
+    deny_tokens: HashSet<String>,
+    allow_tokens: HashSet<String>,
+    deny_selectors: Vec<Selector>,
+    allow_selectors: Vec<Selector>,
+    /// Rules replacing these ones for a host pattern (`example.com` or `*.example.com`)
+    hosts: Vec<(String, RemovalRules)>,
+}
+
+impl Default for RemovalRules {
+    fn default() -> Self {
+        DEFAULT_DENY_TOKENS
+            .into_iter()
+            .fold(Self::empty(), |rules, token| rules.deny_token(token))
+    }
+}
+
+impl RemovalRules {
+    /// Rules removing nothing
+    pub fn empty() -> Self {
+        Self {
+            deny_tokens: HashSet::new(),
+            allow_tokens: HashSet::new(),
+            deny_selectors: Vec::new(),
+            allow_selectors: Vec::new(),
+            hosts: Vec::new(),
+        }
+    }
+
+    /// Removes `div` and `aside` elements with the class name
+    pub fn deny_token(mut self, token: &str) -> Self {
+        self.deny_tokens.insert(token.to_ascii_lowercase());
+        self
+    }
+
+    /// Keeps elements with the class name even when a deny rule matches
+    pub fn allow_token(mut self, token: &str) -> Self {
+        self.allow_tokens.insert(token.to_ascii_lowercase());
+        self
+    }
+
+    /// Removes elements matching the CSS selector
+    pub fn deny_selector(mut self, selector: &str) -> Result<Self, ConvertError> {
+        self.deny_selectors.push(Selector::parse(selector)?);
+        Ok(self)
+    }
+
+    /// Keeps elements matching the CSS selector even when a deny rule matches
+    pub fn allow_selector(mut self, selector: &str) -> Result<Self, ConvertError> {
+        self.allow_selectors.push(Selector::parse(selector)?);
+        Ok(self)
+    }
+
+    /// Uses `rules` instead of these ones for pages of the host, `*.example.com` matches subdomains.
+    /// The first matching host wins.
+    pub fn host(mut self, pattern: &str, rules: RemovalRules) -> Self {
+        self.hosts.push((pattern.to_ascii_lowercase(), rules));
+        self
+    }
+
+    /// Rules applying to the page at `url`
+    pub(crate) fn for_url(&self, url: &str) -> &RemovalRules {
+        if self.hosts.is_empty() {
+            return self;
+        }
+        url::host(url)
+            .and_then(|host| {
+                self.hosts
+                    .iter()
+                    .find(|(pattern, _)| url::host_matches(pattern, &host))
+            })
+            .map_or(self, |(_, rules)| rules.for_url(url))
+    }
+
+    /// Checks whether the element is left out of the output
+    pub(crate) fn should_remove(&self, dom: &Dom, id: NodeId) -> bool {
+        let Ok((tag, attrs)) = dom.get_element_data(id) else {
+            return false;
+        };
+        // the document itself is never removed
+        if matches!(tag.local.as_ref(), "html" | "body") {
+            return false;
+        }
+
+        let tokens: Vec<String> = attrs
+            .get("class")
+            .into_iter()
+            .flat_map(|class| class.split_ascii_whitespace())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        let denied = (TOKEN_TAGS.contains(&tag.local.as_ref())
+            && tokens.iter().any(|token| self.deny_tokens.contains(token)))
+            || self
+                .deny_selectors
+                .iter()
+                .any(|selector| selector.matches(dom, id));
+        if !denied {
+            return false;
+        }
+        let allowed = tokens.iter().any(|token| self.allow_tokens.contains(token))
+            || self
+                .allow_selectors
+                .iter()
+                .any(|selector| selector.matches(dom, id));
+        !allowed
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
 
-    /// Test case sensitivity
+    fn first_removed(rules: &RemovalRules, html: &str, tag: &str) -> bool {
+        let dom = parser::parse_html(html).expect("Failed to parse HTML");
+        let id = dom
+            .find_element_by_tag(dom.document, tag)
+            .expect("No element");
+        rules.should_remove(&dom, id)
+    }
+
+    fn first_div_removed(rules: &RemovalRules, html: &str) -> bool {
+        first_removed(rules, html, "div")
+    }
+
+    /// Default rules, matching whole class names case-insensitively
+    #[rstest]
+    #[case(r#"<div class="sidebar">"#, true)]
+    #[case(r#"<div class="SIDEBAR">"#, true)]
+    #[case(r#"<div class="SideBar">"#, true)]
+    #[case(r#"<div class="author">"#, true)]
+    #[case(r#"<div class="PUBLICATION">"#, true)]
+    #[case(r#"<div class="mobile">"#, true)]
+    #[case(r#"<div class="navigation">"#, true)]
+    #[case(r#"<div class="multiple sidebar classes here">"#, true)]
+    #[case(r#"<div class="class1 author class2">"#, true)]
+    #[case(r#"<div class="widget userinfo panel">"#, true)]
+    #[case(r#"<div class="content topics section">"#, true)]
+    #[case(r#"<div class="main comment area">"#, true)]
+    #[case(r#"<div class="bottom footer content">"#, true)]
+    #[case(r#"<div class="ad advertisement space">"#, true)]
+    #[case(r#"<div class="media social links">"#, true)]
+    #[case(r#"<div class="author-note">"#, false)]
+    #[case(r#"<div class="publication-body">"#, false)]
+    #[case(r#"<div class="my-sidebar-widget">"#, false)]
+    #[case(r#"<div class="prefix-mobile-suffix">"#, false)]
+    #[case(r#"<div id="comment">"#, false)]
+    #[case(r#"<div class="note warning info">"#, false)]
+    #[case(r#"<div class="main article content">"#, false)]
+    #[case(r#"<div class="">"#, false)]
+    #[case(r#"<div class="   ">"#, false)]
+    #[case(r#"<div>"#, false)]
+    fn test_default_rules(#[case] html: &str, #[case] expected: bool) {
+        assert_eq!(first_div_removed(&RemovalRules::default(), html), expected);
+    }
+
+    /// Deny tokens only apply to containers, selectors to any element
+    #[rstest]
+    #[case(RemovalRules::default(), r#"<aside class="sidebar">"#, "aside", true)]
+    #[case(
+        RemovalRules::default(),
+        r#"<section class="comment">"#,
+        "section",
+        false
+    )]
+    #[case(
+        RemovalRules::default(),
+        r#"<p>By <a class="author" href="/u/jane">Jane</a>"#,
+        "a",
+        false
+    )]
+    #[case(
+        RemovalRules::default(),
+        r#"<p><em id="footer">note</em>"#,
+        "em",
+        false
+    )]
+    #[case(
+        RemovalRules::default(),
+        r#"<ul><li class="comment">Item</li></ul>"#,
+        "li",
+        false
+    )]
+    #[case(RemovalRules::default(), r#"<main id="mobile">"#, "main", false)]
+    #[case(
+        RemovalRules::empty().deny_selector("li.comment").unwrap(),
+        r#"<ul><li class="comment">Item</li></ul>"#,
+        "li",
+        true
+    )]
+    #[case(
+        RemovalRules::empty().deny_selector("span.author").unwrap().allow_token("keep"),
+        r#"<p><span class="author keep">Jane</span>"#,
+        "span",
+        false
+    )]
+    fn test_rule_scope(
+        #[case] rules: RemovalRules,
+        #[case] html: &str,
+        #[case] tag: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(first_removed(&rules, html, tag), expected);
+    }
+
+    #[rstest]
+    #[case(RemovalRules::empty(), r#"<div class="sidebar">"#, false)]
+    #[case(RemovalRules::default().allow_token("Author"), r#"<div class="author">"#, false)]
+    #[case(RemovalRules::default().allow_token("bio"), r#"<div class="author bio">"#, false)]
+    #[case(RemovalRules::default().allow_token("bio"), r#"<div class="author">"#, true)]
+    #[case(RemovalRules::empty().deny_token("related"), r#"<div class="related">"#, true)]
+    #[case(
+        RemovalRules::empty().deny_selector("[class*=sidebar]").unwrap(),
+        r#"<div class="my-sidebar-widget">"#,
+        true
+    )]
+    #[case(
+        RemovalRules::empty().deny_selector("aside div, .ad").unwrap(),
+        r#"<aside><div>"#,
+        true
+    )]
+    #[case(
+        RemovalRules::default().allow_selector("article div").unwrap(),
+        r#"<article><div class="share">"#,
+        false
+    )]
+    #[case(
+        RemovalRules::default().allow_selector("article div").unwrap(),
+        r#"<section><div class="share">"#,
+        true
+    )]
+    fn test_custom_rules(#[case] rules: RemovalRules, #[case] html: &str, #[case] expected: bool) {
+        assert_eq!(first_div_removed(&rules, html), expected);
+    }
+
+    #[test]
+    fn test_invalid_selector() {
+        assert!(matches!(
+            RemovalRules::empty().deny_selector("div["),
+            Err(ConvertError::InvalidSelector(_))
+        ));
+    }
+
+    #[test]
+    fn test_root_is_kept() {
+        let rules = RemovalRules::empty().deny_selector("*").unwrap();
+        let dom = parser::parse_html(r#"<body class="mobile"><p>Text</p></body>"#)
+            .expect("Failed to parse HTML");
+        let body = dom.find_body().expect("No body");
+        assert!(!rules.should_remove(&dom, body));
+    }
+
+    /// Host overrides
     #[rstest]
-    #[case("sidebar", true)]
-    #[case("SIDEBAR", true)]
-    #[case("Sidebar", true)]
-    #[case("SideBar", true)]
-    #[case("my-sidebar-widget", true)]
-    #[case("author", true)]
-    #[case("AUTHOR", true)]
-    #[case("publication", true)]
-    #[case("PUBLICATION", true)]
-    #[case("mobile", true)]
-    #[case("MOBILE", true)]
-    #[case("note", false)]
-    #[case("warning", false)]
-    #[case("info", false)]
-    #[case("navigation", true)]
-    #[case("multiple sidebar classes here", true)]
-    #[case("class1 author class2", true)]
-    #[case("prefix-mobile-suffix", true)]
-    #[case("valid-share-button", true)]
-    #[case("widget userinfo panel", true)]
-    #[case("content topics section", true)]
-    #[case("main comment area", true)]
-    #[case("top navigation bar", true)]
-    #[case("bottom footer content", true)]
-    #[case("ad advertisement space", true)]
-    #[case("media social links", true)]
-    #[case("note warning info", false)]
-    #[case("primary secondary content", false)]
-    #[case("main article content", false)]
-    #[case("header title text", false)]
-    #[case("valid content classes", false)]
-    #[case("", false)] // Empty string
-    #[case("   ", false)] // Whitespace only
-    fn test_should_ignore_class(#[case] class_value: &str, #[case] expected: bool) {
-        assert_eq!(should_ignore_class(class_value), expected);
+    #[case("https://example.com/post", r#"<div class="related">"#, true)]
+    #[case("https://example.com/post", r#"<div class="sidebar">"#, false)]
+    #[case("https://blog.example.org/post", r#"<div class="sidebar">"#, false)]
+    #[case("https://example.org/post", r#"<div class="sidebar">"#, true)]
+    #[case("https://other.com/post", r#"<div class="sidebar">"#, true)]
+    #[case("https://other.com/post", r#"<div class="related">"#, false)]
+    #[case("not a url", r#"<div class="sidebar">"#, true)]
+    fn test_host_rules(#[case] url: &str, #[case] html: &str, #[case] expected: bool) {
+        let rules = RemovalRules::default()
+            .host("example.com", RemovalRules::empty().deny_token("related"))
+            .host("*.example.org", RemovalRules::empty());
+        assert_eq!(first_div_removed(rules.for_url(url), html), expected);
     }
 }
@@ -1,4 +1,5 @@
-use html_to_markdown::convert;
+use html_to_markdown::{ConvertOptions, RemovalRules, convert, convert_with_options};
+use rstest::rstest;
 use std::{fs, path::PathBuf};
 
 fn read_from_crate(rel: &str) -> std::io::Result<String> {
@@ -49,3 +50,123 @@ fn test_zenn_article1_extraction() {
 
     assert_eq!(body, markdown)
 }
+
+#[test]
+fn test_base_href_resolution() {
+    let html = r#"<html><head><base href="/docs/v2/"></head><body>
+<p><a href="guide/intro.html">Intro</a> and <a href="../v1/">Old docs</a></p>
+<p><img src="img/diagram.png" alt="Diagram"></p>
+</body></html>"#;
+
+    let result =
+        convert("https://example.com/blog/post", html, &[]).expect("failed to convert HTML");
+
+    assert_eq!(
+        result,
+        "[Intro](https://example.com/docs/v2/guide/intro.html) and [Old docs](https://example.com/docs/v1/)\n\n![Diagram](https://example.com/docs/v2/img/diagram.png)\n\n"
+    );
+}
+
+#[rstest]
+#[case("https://example.com/post", "Post body\n\nWritten by Alice\n\n")]
+#[case("https://blog.example.com/post", "Post body\n\nShare\n\n")]
+fn test_removal_rules(#[case] url: &str, #[case] expected: &str) {
+    let html = r#"<html><body><article>
+<p>Post body</p>
+<div class="author">Written by Alice</div>
+<div class="share-buttons">Share</div>
+</article></body></html>"#;
+    let rules = RemovalRules::default()
+        .allow_token("author")
+        .deny_selector("[class^=share]")
+        .expect("invalid selector")
+        .host("*.example.com", RemovalRules::default());
+    let options = ConvertOptions::default().removal_rules(rules);
+
+    let result = convert_with_options(url, html, &[], &options).expect("failed to convert HTML");
+
+    assert_eq!(result, expected);
+}
+
+/// the default removal rules keep inline elements, list items and the render root
+#[rstest]
+#[case(
+    r#"<p>By <a class="author" href="/u/jane">Jane</a>, <em id="footer">note</em></p>"#,
+    "By [Jane](https://example.com/u/jane), *note*\n\n"
+)]
+#[case(
+    r#"<ul><li class="comment">First</li><li class="comment">Second</li></ul>"#,
+    "- First\n- Second\n\n"
+)]
+#[case(
+    r#"<main id="mobile"><article><p>Article body</p></article></main>"#,
+    "Article body\n\n"
+)]
+#[case(r#"<div class="sidebar"><p>Sidebar</p></div><p>Body</p>"#, "Body\n\n")]
+fn test_default_removal_rules(#[case] html: &str, #[case] expected: &str) {
+    let result = convert("https://example.com/post", html, &[]).expect("failed to convert HTML");
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_removal_rules_keep_root() {
+    let html = r#"<html><body><article class="post"><p>Article body</p><p class="ad">Sponsored</p></article></body></html>"#;
+    let options = ConvertOptions::default().removal_rules(
+        RemovalRules::empty()
+            .deny_selector("article, .ad")
+            .expect("invalid selector"),
+    );
+
+    let result = convert_with_options("https://example.com/post", html, &[], &options)
+        .expect("failed to convert HTML");
+
+    assert_eq!(result, "Article body\n\n");
+}
+
+#[test]
+fn test_malformed_html() {
+    // text misplaced in a table is foster-parented, unclosed formatting is reconstructed
+    let html =
+        "<table><tr><td>Cell</td></tr>Stray note</table><p><b>Bold<p>still bold</b> plain</p>";
+
+    let result = convert("https://example.com/post", html, &[]).expect("failed to convert HTML");
+
+    assert!(result.contains("Stray note"), "{result}");
+    assert!(result.contains("**still bold** plain"), "{result}");
+}
+
+#[test]
+fn test_main_content_without_article() {
+    let html = r#"<html><body>
+<div class="global-menu"><ul><li><a href="/">Home</a></li><li><a href="/about">About</a></li></ul></div>
+<div class="post-content"><h2>Getting started</h2>
+<p>Install the toolchain with rustup, then create a project with cargo new, build it and run it.</p>
+</div>
+<div class="related-posts"><ul><li><a href="/other">Another post about Rust, tooling, and more</a></li></ul></div>
+</body></html>"#;
+
+    let result = convert("https://example.com/post", html, &[]).expect("failed to convert HTML");
+
+    assert_eq!(
+        result,
+        "## Getting started\n\nInstall the toolchain with rustup, then create a project with cargo new, build it and run it.\n\n"
+    );
+}
+
+/// site extractors choose the content root, strip the site chrome and fill in front-matter
+#[rstest]
+#[case("qiita", "https://qiita.com/alice/items/0123abcd")]
+#[case("zenn", "https://zenn.dev/bob/articles/tokio-async")]
+#[case("note", "https://note.com/carol/n/n0123456789ab")]
+#[case("hatena", "https://dave.hatenablog.com/entry/2024/03/01/120000")]
+#[case("github_readme", "https://github.com/owner/repo")]
+#[case("github_issue", "https://github.com/owner/repo/issues/42")]
+fn test_site_extractors(#[case] site: &str, #[case] url: &str) {
+    let html = load_file(&format!("fixtures/sites/{site}.html"));
+    let keys = ["title", "author", "tags", "published", "source"];
+
+    let result = convert(url, &html, &keys).expect("failed to convert HTML");
+
+    assert_eq!(result, load_file(&format!("fixtures/sites/{site}.md")));
+}